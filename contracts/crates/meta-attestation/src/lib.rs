@@ -0,0 +1,282 @@
+//! ed25519 attestation checks shared by the $META programs.
+//!
+//! An off-chain signer (the trading engine or the mint attestor) signs a
+//! domain-separated message, and the program verifies that the instruction
+//! before it is an ed25519 program instruction carrying that signature.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+/// Leading bytes of every attested message
+pub const DOMAIN_PREFIX: &[u8] = b"meta-attestation:v1:";
+
+/// Why an attestation was not accepted; each program maps these onto its own error codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttestationError {
+    /// The previous instruction is not an ed25519 signature check
+    Missing,
+    /// The signature check does not cover the expected signer and message
+    Invalid,
+}
+
+/// Bytes an attestor signs: the domain prefix, the verifying program's ID, the
+/// Borsh-encoded message `kind` and the Borsh-encoded `payload`
+///
+/// Binding the program ID and kind keeps a signature from being replayed against
+/// another program or reinterpreted as a different message type.
+pub fn attestation_message(program_id: &Pubkey, kind: &str, payload: &[u8]) -> Vec<u8> {
+    let mut message =
+        Vec::with_capacity(DOMAIN_PREFIX.len() + 32 + 4 + kind.len() + payload.len());
+    message.extend_from_slice(DOMAIN_PREFIX);
+    message.extend_from_slice(program_id.as_ref());
+    message.extend_from_slice(&(kind.len() as u32).to_le_bytes());
+    message.extend_from_slice(kind.as_bytes());
+    message.extend_from_slice(payload);
+    message
+}
+
+/// Check that the previous instruction is an ed25519 signature by `signer` over `message`
+pub fn verify_ed25519_attestation(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> std::result::Result<(), AttestationError> {
+    // Layout of the ed25519 program instruction data:
+    // [num_signatures: u8, padding: u8, offsets: 7 x u16, ...pubkey, signature, message]
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+
+    let current_index =
+        load_current_index_checked(instructions_sysvar).map_err(|_| AttestationError::Missing)?;
+    if current_index == 0 {
+        return Err(AttestationError::Missing);
+    }
+
+    let ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)
+        .map_err(|_| AttestationError::Missing)?;
+    if ix.program_id != ed25519_program::ID || !ix.accounts.is_empty() {
+        return Err(AttestationError::Missing);
+    }
+
+    let data = &ix.data;
+    if data.len() < OFFSETS_START + OFFSETS_LEN || data[0] != 1 {
+        return Err(AttestationError::Invalid);
+    }
+
+    // Offsets: signature, signature ix, public key, public key ix, message, message size, message ix
+    let read_u16 = |field: usize| {
+        let at = OFFSETS_START + field * 2;
+        u16::from_le_bytes([data[at], data[at + 1]])
+    };
+    let public_key_offset = read_u16(2) as usize;
+    let message_offset = read_u16(4) as usize;
+    let message_size = read_u16(5) as usize;
+
+    // Signature, key and message must all live in the ed25519 instruction itself
+    if read_u16(1) != u16::MAX || read_u16(3) != u16::MAX || read_u16(6) != u16::MAX {
+        return Err(AttestationError::Invalid);
+    }
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(AttestationError::Invalid)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(AttestationError::Invalid)?;
+
+    if public_key != signer.as_ref() || signed_message != message {
+        return Err(AttestationError::Invalid);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::sysvar::instructions::{
+        self, construct_instructions_data, store_current_index, BorrowedAccountMeta,
+        BorrowedInstruction,
+    };
+
+    const PUBLIC_KEY_OFFSET: u16 = 16;
+    const SIGNATURE_OFFSET: u16 = PUBLIC_KEY_OFFSET + 32;
+    const MESSAGE_OFFSET: u16 = SIGNATURE_OFFSET + 64;
+
+    /// ed25519 program instruction data with a single signature laid out after the
+    /// offsets; the signature bytes are left zeroed since only the runtime checks them
+    fn ed25519_data(signer: &Pubkey, message: &[u8]) -> Vec<u8> {
+        let offsets = [
+            SIGNATURE_OFFSET,
+            u16::MAX,
+            PUBLIC_KEY_OFFSET,
+            u16::MAX,
+            MESSAGE_OFFSET,
+            message.len() as u16,
+            u16::MAX,
+        ];
+        let mut data = vec![1, 0];
+        for offset in offsets {
+            data.extend_from_slice(&offset.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[0; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    fn set_offset(data: &mut [u8], field: usize, value: u16) {
+        let at = 2 + field * 2;
+        data[at..at + 2].copy_from_slice(&value.to_le_bytes());
+    }
+
+    /// Run the check as the instruction after one `program_id` instruction with `data`
+    fn verify_after(
+        program_id: &Pubkey,
+        data: &[u8],
+        signer: &Pubkey,
+        message: &[u8],
+    ) -> std::result::Result<(), AttestationError> {
+        verify_at(program_id, data, 1, signer, message)
+    }
+
+    fn verify_at(
+        program_id: &Pubkey,
+        data: &[u8],
+        current_index: u16,
+        signer: &Pubkey,
+        message: &[u8],
+    ) -> std::result::Result<(), AttestationError> {
+        let caller = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let mut sysvar_data = construct_instructions_data(&[
+            BorrowedInstruction {
+                program_id,
+                accounts: vec![],
+                data,
+            },
+            BorrowedInstruction {
+                program_id: &caller,
+                accounts: vec![BorrowedAccountMeta {
+                    pubkey: &payer,
+                    is_signer: true,
+                    is_writable: true,
+                }],
+                data: &[],
+            },
+        ]);
+        store_current_index(&mut sysvar_data, current_index);
+
+        let key = instructions::ID;
+        let owner = Pubkey::default();
+        let mut lamports = 0;
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut sysvar_data,
+            &owner,
+            false,
+            0,
+        );
+        verify_ed25519_attestation(&info, signer, message)
+    }
+
+    #[test]
+    fn message_binds_prefix_program_kind_and_payload() {
+        let program_id = Pubkey::new_unique();
+        let message = attestation_message(&program_id, "Kind", &[7, 8]);
+
+        let mut expected = b"meta-attestation:v1:".to_vec();
+        expected.extend_from_slice(program_id.as_ref());
+        expected.extend_from_slice(&[4, 0, 0, 0]);
+        expected.extend_from_slice(b"Kind");
+        expected.extend_from_slice(&[7, 8]);
+        assert_eq!(message, expected);
+
+        assert_ne!(message, attestation_message(&Pubkey::new_unique(), "Kind", &[7, 8]));
+        assert_ne!(message, attestation_message(&program_id, "Other", &[7, 8]));
+    }
+
+    #[test]
+    fn accepts_a_matching_signature() {
+        let signer = Pubkey::new_unique();
+        let message = attestation_message(&Pubkey::new_unique(), "Kind", b"payload");
+        let data = ed25519_data(&signer, &message);
+
+        assert_eq!(verify_after(&ed25519_program::ID, &data, &signer, &message), Ok(()));
+    }
+
+    #[test]
+    fn rejects_a_missing_previous_instruction() {
+        let signer = Pubkey::new_unique();
+        let data = ed25519_data(&signer, b"message");
+
+        assert_eq!(
+            verify_at(&ed25519_program::ID, &data, 0, &signer, b"message"),
+            Err(AttestationError::Missing)
+        );
+        assert_eq!(
+            verify_after(&Pubkey::new_unique(), &data, &signer, b"message"),
+            Err(AttestationError::Missing)
+        );
+    }
+
+    #[test]
+    fn rejects_other_signature_counts() {
+        let signer = Pubkey::new_unique();
+        for count in [0, 2] {
+            let mut data = ed25519_data(&signer, b"message");
+            data[0] = count;
+            assert_eq!(
+                verify_after(&ed25519_program::ID, &data, &signer, b"message"),
+                Err(AttestationError::Invalid)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_offsets_into_another_instruction() {
+        let signer = Pubkey::new_unique();
+        // Signature, public key and message instruction index fields
+        for field in [1, 3, 6] {
+            let mut data = ed25519_data(&signer, b"message");
+            set_offset(&mut data, field, 1);
+            assert_eq!(
+                verify_after(&ed25519_program::ID, &data, &signer, b"message"),
+                Err(AttestationError::Invalid)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_another_signer_or_message() {
+        let signer = Pubkey::new_unique();
+        let data = ed25519_data(&signer, b"message");
+
+        assert_eq!(
+            verify_after(&ed25519_program::ID, &data, &Pubkey::new_unique(), b"message"),
+            Err(AttestationError::Invalid)
+        );
+        assert_eq!(
+            verify_after(&ed25519_program::ID, &data, &signer, b"massage"),
+            Err(AttestationError::Invalid)
+        );
+    }
+
+    #[test]
+    fn rejects_offsets_past_the_data() {
+        let signer = Pubkey::new_unique();
+        let mut data = ed25519_data(&signer, b"message");
+        set_offset(&mut data, 5, 64);
+
+        assert_eq!(
+            verify_after(&ed25519_program::ID, &data, &signer, b"message"),
+            Err(AttestationError::Invalid)
+        );
+    }
+}
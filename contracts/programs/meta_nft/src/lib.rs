@@ -1,38 +1,131 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::hash::hash;
 use anchor_spl::{
-    associated_token::AssociatedToken,
+    associated_token::{create_idempotent, AssociatedToken, Create},
     metadata::{
-        create_master_edition_v3, create_metadata_accounts_v3,
-        CreateMasterEditionV3, CreateMetadataAccountsV3, Metadata,
+        burn_nft, create_master_edition_v3, create_metadata_accounts_v3,
+        mint_new_edition_from_master_edition_via_token, update_metadata_accounts_v2,
+        verify_sized_collection_item, BurnNft, CreateMasterEditionV3, CreateMetadataAccountsV3,
+        Metadata, MetadataAccount, MintNewEditionFromMasterEditionViaToken,
+        UpdateMetadataAccountsV2, VerifySizedCollectionItem,
     },
-    token::{mint_to, Mint, MintTo, Token, TokenAccount},
+    token::{
+        close_account, mint_to, transfer, CloseAccount, Mint, MintTo, Token, TokenAccount,
+        Transfer,
+    },
+    token_2022::spl_token_2022::{
+        self,
+        extension::{BaseStateWithExtensions, StateWithExtensions},
+        instruction::AuthorityType,
+    },
+    token_interface::{
+        self,
+        spl_pod::optional_keys::OptionalNonZeroPubkey,
+        spl_token_metadata_interface::state::{Field, TokenMetadata},
+        token_metadata_initialize, token_metadata_update_field, Token2022,
+        TokenMetadataInitialize, TokenMetadataUpdateField,
+    },
+};
+use meta_attestation::{attestation_message, verify_ed25519_attestation, AttestationError};
+use meta_treasury::program::MetaTreasury;
+use mpl_token_metadata::instructions::{
+    BurnV1CpiBuilder, CreateV1CpiBuilder, MintV1CpiBuilder, TransferV1CpiBuilder,
+    VerifyCollectionV1CpiBuilder,
 };
-use mpl_token_metadata::types::DataV2;
+use mpl_token_metadata::types::{Collection, CollectionDetails, DataV2, PrintSupply, TokenStandard};
 
 declare_id!("MetaNft111111111111111111111111111111111111");
 
+/// Metaplex allows at most five creators per metadata account
+pub const MAX_CREATORS: usize = 5;
+/// Upper bound on royalties any strategy NFT can carry (10%)
+pub const MAX_SELLER_FEE_BASIS_POINTS: u16 = 1_000;
+/// Leaves room for " #<edition number>" inside Metaplex's 32 byte name limit
+pub const MAX_NAME_PREFIX_LEN: usize = 22;
+pub const MAX_SYMBOL_LEN: usize = 10;
+/// Metaplex's URI length limit
+pub const MAX_URI_LEN: usize = 200;
+/// Number of performance snapshots kept per strategy NFT
+pub const HISTORY_CAPACITY: usize = 32;
+/// Leaves room for "<strategy_id>.json" inside Metaplex's 200 byte URI limit
+pub const MAX_BASE_URI_LEN: usize = 128;
+/// 100% expressed in basis points
+pub const MAX_BPS: u64 = 10_000;
+/// Sharpe ratios are stored in milli-units and capped at +/-100.0
+pub const MAX_SHARPE_RATIO_MILLI: i32 = 100_000;
+/// How long the engine has to fulfil a breeding request before it can be refunded
+pub const DEFAULT_BREEDING_TIMEOUT_SECONDS: i64 = 24 * 60 * 60;
+
 #[program]
 pub mod meta_nft {
     use super::*;
 
-    /// Initialize the NFT collection config
+    /// Initialize the NFT collection config and mint the collection NFT it owns
     pub fn initialize_collection(
         ctx: Context<InitializeCollection>,
         name: String,
         symbol: String,
         uri: String,
+        name_prefix: String,
+        base_uri: String,
     ) -> Result<()> {
+        validate_metadata_config(&name_prefix, &symbol, &base_uri)?;
+
+        let config_key = ctx.accounts.collection_config.key();
         let config = &mut ctx.accounts.collection_config;
         config.authority = ctx.accounts.authority.key();
         config.total_minted = 0;
+        config.total_burned = 0;
+        config.breeding_fee_lamports = 50_000_000; // 0.05 SOL default
+        config.edition_price_lamports = 20_000_000; // 0.02 SOL default
+        config.token_standard = NftStandard::NonFungible;
+        config.rule_set = None;
+        config.breeding_timeout_seconds = DEFAULT_BREEDING_TIMEOUT_SECONDS;
+        config.total_breeding_requests = 0;
         config.mint_price_lamports = 100_000_000; // 0.1 SOL default
         config.is_active = true;
         config.bump = ctx.bumps.collection_config;
+        config.collection_mint = ctx.accounts.collection_mint.key();
+        config.name_prefix = name_prefix;
+        config.symbol = symbol.clone();
+        config.base_uri = base_uri;
+        config.seller_fee_basis_points = 500; // 5% royalty
+        config.creators = vec![CreatorShare {
+            address: config_key,
+            share: 100,
+        }];
+        let creators = config.metadata_creators(config_key);
+        let seller_fee_basis_points = config.seller_fee_basis_points;
+
+        mint_collection_nft(
+            &ctx.accounts.collection_config.to_account_info(),
+            ctx.bumps.collection_config,
+            &ctx.accounts.collection_mint,
+            &ctx.accounts.collection_token_account,
+            &ctx.accounts.collection_metadata,
+            &ctx.accounts.collection_master_edition,
+            &ctx.accounts.authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.metadata_program,
+            &ctx.accounts.system_program,
+            &ctx.accounts.rent,
+            DataV2 {
+                name: name.clone(),
+                symbol: symbol.clone(),
+                uri,
+                seller_fee_basis_points,
+                creators: Some(creators),
+                collection: None,
+                uses: None,
+            },
+        )?;
 
         emit!(CollectionInitialized {
-            authority: config.authority,
-            name: name.clone(),
-            symbol: symbol.clone(),
+            authority: ctx.accounts.authority.key(),
+            collection_mint: ctx.accounts.collection_mint.key(),
+            name,
+            symbol,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
@@ -40,268 +133,3173 @@ pub mod meta_nft {
     }
 
     /// Mint a Strategy NFT
+    ///
+    /// The instruction immediately before this one must be an ed25519 program
+    /// instruction in which the collection `attestor` signs the Borsh-encoded
+    /// `MintAttestation` built from these arguments.
+    ///
+    /// When `genes` is given it is stored in the `strategy_genes` account and must
+    /// hash to `genes_hash`. Each genes hash can only be minted once.
+    ///
+    /// `parent_a` and `parent_b` are the mints of the strategy's parents; each one set
+    /// must be backed by the matching `StrategyNftData` account.
+    ///
+    /// `royalty_override` replaces the collection's royalties and creator split for this
+    /// strategy only, e.g. to pay its discoverer.
+    #[allow(clippy::too_many_arguments)]
     pub fn mint_strategy_nft(
         ctx: Context<MintStrategyNft>,
         strategy_id: String,
-        genes_hash: String,
-        name: String,
-        symbol: String,
-        uri: String,
+        genes_hash: [u8; 32],
         archetype: String,
         generation: u32,
         fitness_score: u64,
         total_pnl: i64,
         win_rate: u64,
         trades_executed: u32,
+        sharpe_ratio_milli: i32,
+        max_drawdown_bps: u16,
+        avg_hold_time_secs: u32,
+        parent_a: Option<Pubkey>,
+        parent_b: Option<Pubkey>,
+        royalty_override: Option<RoyaltyOverride>,
+        expires_at: i64,
+        genes: Option<StrategyGenes>,
     ) -> Result<()> {
-        let config = &mut ctx.accounts.collection_config;
-        
-        require!(config.is_active, MetaNftError::MintingPaused);
-        
-        // Verify payment
-        let rent = Rent::get()?;
+        // Strategy stats must come from the engine, not the minter
+        let attestation = MintAttestation {
+            strategy_id: strategy_id.clone(),
+            genes_hash,
+            archetype,
+            generation,
+            fitness_score,
+            total_pnl,
+            win_rate,
+            trades_executed,
+            sharpe_ratio_milli,
+            max_drawdown_bps,
+            avg_hold_time_secs,
+            parent_a,
+            parent_b,
+            royalty_override: royalty_override.clone(),
+            owner: ctx.accounts.recipient.key(),
+            expires_at,
+        };
+        verify_mint_request(
+            &ctx.accounts.collection_config,
+            &ctx.accounts.instructions_sysvar,
+            &attestation,
+            &genes,
+            ctx.accounts.strategy_genes.is_some(),
+            &ctx.accounts.parent_a_nft,
+            &ctx.accounts.parent_b_nft,
+        )?;
+
+        let config = &ctx.accounts.collection_config;
         let required_lamports = config.mint_price_lamports;
-        
-        // Transfer mint fee to treasury
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.payer.to_account_info(),
-                    to: ctx.accounts.treasury.to_account_info(),
-                },
-            ),
+        let collection_mint = config.collection_mint;
+        let max_editions = read_edition_supply(&ctx.accounts.edition_supply)?;
+
+        // Book the mint fee as treasury revenue
+        deposit_mint_fee(
+            &ctx.accounts.treasury_program,
+            &ctx.accounts.treasury,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
             required_lamports,
         )?;
 
-        // Mint NFT token
         let seeds = &[
             b"collection".as_ref(),
-            &[config.bump],
+            &[ctx.accounts.collection_config.bump],
         ];
         let signer_seeds = &[&seeds[..]];
 
-        mint_to(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                MintTo {
-                    mint: ctx.accounts.mint.to_account_info(),
-                    to: ctx.accounts.token_account.to_account_info(),
-                    authority: ctx.accounts.collection_config.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            1,
+        // Metadata from the collection's branding and the strategy's royalties
+        let config = &ctx.accounts.collection_config;
+        let token_standard = config.token_standard;
+        let (seller_fee_basis_points, creators) = config.royalties(config.key(), &royalty_override);
+        let data = DataV2 {
+            name: format!("{} #{}", config.name_prefix, config.total_minted.checked_add(1).unwrap()),
+            symbol: config.symbol.clone(),
+            uri: format!("{}{}.json", config.base_uri, strategy_id),
+            seller_fee_basis_points,
+            creators: Some(creators),
+            collection: Some(Collection {
+                verified: false,
+                key: collection_mint,
+            }),
+            uses: None,
+        };
+
+        match token_standard {
+            NftStandard::NonFungible => {
+                // Mint NFT token
+                mint_to(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        MintTo {
+                            mint: ctx.accounts.mint.to_account_info(),
+                            to: ctx.accounts.token_account.to_account_info(),
+                            authority: ctx.accounts.collection_config.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    1,
+                )?;
+
+                create_metadata_accounts_v3(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.metadata_program.to_account_info(),
+                        CreateMetadataAccountsV3 {
+                            metadata: ctx.accounts.metadata.to_account_info(),
+                            mint: ctx.accounts.mint.to_account_info(),
+                            mint_authority: ctx.accounts.collection_config.to_account_info(),
+                            payer: ctx.accounts.payer.to_account_info(),
+                            update_authority: ctx.accounts.collection_config.to_account_info(),
+                            system_program: ctx.accounts.system_program.to_account_info(),
+                            rent: ctx.accounts.rent.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    data,
+                    true,
+                    true,
+                    None,
+                )?;
+
+                // Create master edition (makes it an NFT)
+                create_master_edition_v3(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.metadata_program.to_account_info(),
+                        CreateMasterEditionV3 {
+                            edition: ctx.accounts.master_edition.to_account_info(),
+                            mint: ctx.accounts.mint.to_account_info(),
+                            update_authority: ctx.accounts.collection_config.to_account_info(),
+                            mint_authority: ctx.accounts.collection_config.to_account_info(),
+                            payer: ctx.accounts.payer.to_account_info(),
+                            metadata: ctx.accounts.metadata.to_account_info(),
+                            token_program: ctx.accounts.token_program.to_account_info(),
+                            system_program: ctx.accounts.system_program.to_account_info(),
+                            rent: ctx.accounts.rent.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    Some(max_editions), // Max supply of 0 means it's a 1/1
+                )?;
+
+                // Verify membership in the collection (the config PDA is the collection authority)
+                verify_sized_collection_item(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.metadata_program.to_account_info(),
+                        VerifySizedCollectionItem {
+                            payer: ctx.accounts.payer.to_account_info(),
+                            metadata: ctx.accounts.metadata.to_account_info(),
+                            collection_authority: ctx.accounts.collection_config.to_account_info(),
+                            collection_mint: ctx.accounts.collection_mint.to_account_info(),
+                            collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+                            collection_master_edition: ctx.accounts.collection_master_edition.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    None,
+                )?;
+            }
+            NftStandard::ProgrammableNonFungible => {
+                // Editions are printed with the legacy instruction, which pNFT masters reject
+                require!(max_editions == 0, MetaNftError::UnsupportedTokenStandard);
+                mint_programmable_nft(ctx.accounts, data, signer_seeds)?;
+            }
+            // Token-2022 collections mint through `mint_strategy_nft_2022`
+            NftStandard::Token2022 => return err!(MetaNftError::UnsupportedTokenStandard),
+        }
+
+        // Store strategy data on-chain
+        let now = Clock::get()?.unix_timestamp;
+        let mint = ctx.accounts.mint.key();
+        let strategy_nft = &mut ctx.accounts.strategy_nft;
+        strategy_nft.record_mint(
+            &attestation,
+            mint,
+            now,
+            required_lamports,
+            max_editions,
+            token_standard,
+            ctx.bumps.strategy_nft,
+        );
+
+        let history = &mut ctx.accounts.performance_history;
+        history.mint = mint;
+        history.bump = ctx.bumps.performance_history;
+        history.push(strategy_nft.snapshot());
+
+        let registry = &mut ctx.accounts.genes_registry;
+        registry.genes_hash = genes_hash;
+        registry.mint = mint;
+        registry.bump = ctx.bumps.genes_registry;
+
+        if let (Some(record), Some(genes)) = (&mut ctx.accounts.strategy_genes, genes) {
+            record.mint = mint;
+            record.genes = genes;
+            record.bump = ctx.bumps.strategy_genes.unwrap();
+        }
+
+        let config = &mut ctx.accounts.collection_config;
+        config.total_minted = config.total_minted.checked_add(1).unwrap();
+
+        emit!(StrategyNftMinted::from_attestation(
+            attestation,
+            mint,
+            ctx.accounts.payer.key(),
+            required_lamports,
+            now,
+        ));
+
+        Ok(())
+    }
+
+    /// Mint a Strategy NFT as a Token-2022 mint that carries its own metadata
+    ///
+    /// Takes the same attestation as `mint_strategy_nft` and is used once the collection
+    /// is switched to `NftStandard::Token2022`. The mint's metadata pointer points at the
+    /// mint itself, whose token-metadata extension holds the name, symbol and URI plus
+    /// `fitness`, `generation` and `archetype` fields. No Metaplex accounts are created,
+    /// so these NFTs have no verified collection, royalties or editions, and the mint
+    /// authority is dropped once the single token exists. `update_nft_royalties`,
+    /// `escrow_master_edition` and `mint_edition` reject them.
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_strategy_nft_2022(
+        ctx: Context<MintStrategyNft2022>,
+        strategy_id: String,
+        genes_hash: [u8; 32],
+        archetype: String,
+        generation: u32,
+        fitness_score: u64,
+        total_pnl: i64,
+        win_rate: u64,
+        trades_executed: u32,
+        sharpe_ratio_milli: i32,
+        max_drawdown_bps: u16,
+        avg_hold_time_secs: u32,
+        parent_a: Option<Pubkey>,
+        parent_b: Option<Pubkey>,
+        expires_at: i64,
+        genes: Option<StrategyGenes>,
+    ) -> Result<()> {
+        let attestation = MintAttestation {
+            strategy_id,
+            genes_hash,
+            archetype,
+            generation,
+            fitness_score,
+            total_pnl,
+            win_rate,
+            trades_executed,
+            sharpe_ratio_milli,
+            max_drawdown_bps,
+            avg_hold_time_secs,
+            parent_a,
+            parent_b,
+            royalty_override: None,
+            owner: ctx.accounts.recipient.key(),
+            expires_at,
+        };
+        verify_mint_request(
+            &ctx.accounts.collection_config,
+            &ctx.accounts.instructions_sysvar,
+            &attestation,
+            &genes,
+            ctx.accounts.strategy_genes.is_some(),
+            &ctx.accounts.parent_a_nft,
+            &ctx.accounts.parent_b_nft,
         )?;
 
-        // Create metadata
-        let data = DataV2 {
+        let config = &ctx.accounts.collection_config;
+        require!(
+            config.token_standard == NftStandard::Token2022,
+            MetaNftError::UnsupportedTokenStandard
+        );
+        let required_lamports = config.mint_price_lamports;
+
+        deposit_mint_fee(
+            &ctx.accounts.treasury_program,
+            &ctx.accounts.treasury,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            required_lamports,
+        )?;
+
+        let seeds = &[b"collection".as_ref(), &[config.bump]];
+        let signer_seeds = &[&seeds[..]];
+        let mint = ctx.accounts.mint.to_account_info();
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let authority = config.to_account_info();
+
+        let name = format!("{} #{}", config.name_prefix, config.total_minted.checked_add(1).unwrap());
+        let uri = format!("{}{}.json", config.base_uri, attestation.strategy_id);
+        let fields = token_metadata_fields(
+            &attestation.archetype,
+            attestation.generation,
+            attestation.fitness_score,
+        );
+
+        // The token program grows the mint for the metadata but doesn't fund the rent
+        let metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey::try_from(Some(config.key()))?,
+            mint: mint.key(),
             name: name.clone(),
-            symbol: symbol.clone(),
+            symbol: config.symbol.clone(),
             uri: uri.clone(),
-            seller_fee_basis_points: 500, // 5% royalty
-            creators: Some(vec![
-                mpl_token_metadata::types::Creator {
-                    address: ctx.accounts.collection_config.key(),
-                    verified: true,
-                    share: 100,
-                },
-            ]),
-            collection: None,
-            uses: None,
+            additional_metadata: fields.clone(),
         };
+        fund_rent(
+            &mint,
+            mint.data_len() + metadata.tlv_size_of()?,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+        )?;
 
-        create_metadata_accounts_v3(
+        token_metadata_initialize(
             CpiContext::new_with_signer(
-                ctx.accounts.metadata_program.to_account_info(),
-                CreateMetadataAccountsV3 {
-                    metadata: ctx.accounts.metadata.to_account_info(),
-                    mint: ctx.accounts.mint.to_account_info(),
-                    mint_authority: ctx.accounts.collection_config.to_account_info(),
-                    payer: ctx.accounts.payer.to_account_info(),
-                    update_authority: ctx.accounts.collection_config.to_account_info(),
-                    system_program: ctx.accounts.system_program.to_account_info(),
-                    rent: ctx.accounts.rent.to_account_info(),
+                token_program.clone(),
+                TokenMetadataInitialize {
+                    token_program_id: token_program.clone(),
+                    metadata: mint.clone(),
+                    update_authority: authority.clone(),
+                    mint_authority: authority.clone(),
+                    mint: mint.clone(),
                 },
                 signer_seeds,
             ),
-            data,
-            true,
-            true,
-            None,
+            name,
+            config.symbol.clone(),
+            uri,
         )?;
+        for (key, value) in fields {
+            token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    TokenMetadataUpdateField {
+                        token_program_id: token_program.clone(),
+                        metadata: mint.clone(),
+                        update_authority: authority.clone(),
+                    },
+                    signer_seeds,
+                ),
+                Field::Key(key),
+                value,
+            )?;
+        }
 
-        // Create master edition (makes it an NFT)
-        create_master_edition_v3(
+        token_interface::mint_to(
             CpiContext::new_with_signer(
-                ctx.accounts.metadata_program.to_account_info(),
-                CreateMasterEditionV3 {
-                    edition: ctx.accounts.master_edition.to_account_info(),
-                    mint: ctx.accounts.mint.to_account_info(),
-                    update_authority: ctx.accounts.collection_config.to_account_info(),
-                    mint_authority: ctx.accounts.collection_config.to_account_info(),
-                    payer: ctx.accounts.payer.to_account_info(),
-                    metadata: ctx.accounts.metadata.to_account_info(),
-                    token_program: ctx.accounts.token_program.to_account_info(),
-                    system_program: ctx.accounts.system_program.to_account_info(),
-                    rent: ctx.accounts.rent.to_account_info(),
+                token_program.clone(),
+                token_interface::MintTo {
+                    mint: mint.clone(),
+                    to: ctx.accounts.token_account.to_account_info(),
+                    authority: authority.clone(),
+                },
+                signer_seeds,
+            ),
+            1,
+        )?;
+
+        // Drop the mint authority so the supply stays at one
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                token_program,
+                token_interface::SetAuthority {
+                    current_authority: authority,
+                    account_or_mint: mint,
                 },
                 signer_seeds,
             ),
-            Some(0), // Max supply of 0 means it's a 1/1
+            AuthorityType::MintTokens,
+            None,
         )?;
 
         // Store strategy data on-chain
+        let now = Clock::get()?.unix_timestamp;
+        let mint = ctx.accounts.mint.key();
+        let strategy_nft = &mut ctx.accounts.strategy_nft;
+        strategy_nft.record_mint(
+            &attestation,
+            mint,
+            now,
+            required_lamports,
+            0,
+            NftStandard::Token2022,
+            ctx.bumps.strategy_nft,
+        );
+
+        let history = &mut ctx.accounts.performance_history;
+        history.mint = mint;
+        history.bump = ctx.bumps.performance_history;
+        history.push(strategy_nft.snapshot());
+
+        let registry = &mut ctx.accounts.genes_registry;
+        registry.genes_hash = genes_hash;
+        registry.mint = mint;
+        registry.bump = ctx.bumps.genes_registry;
+
+        if let (Some(record), Some(genes)) = (&mut ctx.accounts.strategy_genes, genes) {
+            record.mint = mint;
+            record.genes = genes;
+            record.bump = ctx.bumps.strategy_genes.unwrap();
+        }
+
+        let config = &mut ctx.accounts.collection_config;
+        config.total_minted = config.total_minted.checked_add(1).unwrap();
+
+        emit!(StrategyNftMinted::from_attestation(
+            attestation,
+            mint,
+            ctx.accounts.payer.key(),
+            required_lamports,
+            now,
+        ));
+
+        Ok(())
+    }
+
+    /// Copy a Token-2022 strategy NFT's current stats into its token-metadata fields
+    /// (permissionless; `payer` covers any extra rent)
+    pub fn sync_token_metadata(ctx: Context<SyncTokenMetadata>) -> Result<()> {
+        let strategy_nft = &ctx.accounts.strategy_nft;
+        require!(
+            strategy_nft.token_standard == NftStandard::Token2022,
+            MetaNftError::UnsupportedTokenStandard
+        );
+
+        let mint = ctx.accounts.mint.to_account_info();
+        let fields = token_metadata_fields(
+            &strategy_nft.archetype,
+            strategy_nft.generation,
+            strategy_nft.fitness_score,
+        );
+        let new_len = {
+            let data = mint.try_borrow_data()?;
+            let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+            let mut metadata = state.get_variable_len_extension::<TokenMetadata>()?;
+            let old_size = metadata.tlv_size_of()?;
+            for (key, value) in &fields {
+                metadata.set_key_value(key.clone(), value.clone());
+            }
+            data.len() - old_size + metadata.tlv_size_of()?
+        };
+        fund_rent(&mint, new_len, &ctx.accounts.payer, &ctx.accounts.system_program)?;
+
+        let seeds = &[b"collection".as_ref(), &[ctx.accounts.collection_config.bump]];
+        let signer_seeds = &[&seeds[..]];
+        let token_program = ctx.accounts.token_program.to_account_info();
+        for (key, value) in fields {
+            token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    TokenMetadataUpdateField {
+                        token_program_id: token_program.clone(),
+                        metadata: mint.clone(),
+                        update_authority: ctx.accounts.collection_config.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                Field::Key(key),
+                value,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Refresh a strategy's live performance stats (attestor only)
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_strategy_stats(
+        ctx: Context<UpdateStrategyStats>,
+        fitness_score: u64,
+        total_pnl: i64,
+        win_rate: u64,
+        trades_executed: u32,
+        sharpe_ratio_milli: i32,
+        max_drawdown_bps: u16,
+        avg_hold_time_secs: u32,
+        new_uri: Option<String>,
+    ) -> Result<()> {
+        validate_performance(win_rate, sharpe_ratio_milli, max_drawdown_bps)?;
+
+        let now = Clock::get()?.unix_timestamp;
         let strategy_nft = &mut ctx.accounts.strategy_nft;
-        strategy_nft.mint = ctx.accounts.mint.key();
-        strategy_nft.strategy_id = strategy_id.clone();
-        strategy_nft.genes_hash = genes_hash.clone();
-        strategy_nft.owner = ctx.accounts.payer.key();
-        strategy_nft.minted_at = Clock::get()?.unix_timestamp;
-        strategy_nft.mint_price = required_lamports;
-        strategy_nft.archetype = archetype.clone();
-        strategy_nft.generation = generation;
+        require!(
+            strategy_nft.status != StrategyStatus::Dead,
+            MetaNftError::StrategyDead
+        );
         strategy_nft.fitness_score = fitness_score;
         strategy_nft.total_pnl = total_pnl;
         strategy_nft.win_rate = win_rate;
         strategy_nft.trades_executed = trades_executed;
-        strategy_nft.bump = ctx.bumps.strategy_nft;
+        strategy_nft.sharpe_ratio_milli = sharpe_ratio_milli;
+        strategy_nft.max_drawdown_bps = max_drawdown_bps;
+        strategy_nft.avg_hold_time_secs = avg_hold_time_secs;
+        strategy_nft.last_updated = now;
 
-        config.total_minted = config.total_minted.checked_add(1).unwrap();
+        ctx.accounts.performance_history.push(strategy_nft.snapshot());
 
-        emit!(StrategyNftMinted {
-            mint: ctx.accounts.mint.key(),
-            owner: ctx.accounts.payer.key(),
-            strategy_id,
-            genes_hash,
-            archetype,
-            generation,
+        if let Some(uri) = &new_uri {
+            match strategy_nft.token_standard {
+                NftStandard::Token2022 => update_token_metadata_uri(
+                    ctx.accounts.mint.as_ref().ok_or(MetaNftError::MissingMetadata)?,
+                    ctx.accounts.token_program.as_ref().ok_or(MetaNftError::MissingMetadata)?,
+                    &ctx.accounts.attestor,
+                    &ctx.accounts.system_program,
+                    &ctx.accounts.collection_config,
+                    uri.clone(),
+                )?,
+                _ => update_metadata_uri(
+                    &ctx.accounts.metadata_program,
+                    ctx.accounts.metadata.as_ref().ok_or(MetaNftError::MissingMetadata)?,
+                    &ctx.accounts.collection_config,
+                    uri.clone(),
+                )?,
+            }
+        }
+
+        emit!(StrategyStatsUpdated {
+            mint: strategy_nft.mint,
+            strategy_id: strategy_nft.strategy_id.clone(),
             fitness_score,
             total_pnl,
-            mint_price: required_lamports,
-            timestamp: Clock::get()?.unix_timestamp,
+            win_rate,
+            trades_executed,
+            sharpe_ratio_milli,
+            max_drawdown_bps,
+            avg_hold_time_secs,
+            new_uri,
+            timestamp: now,
         });
 
         Ok(())
     }
 
-    /// Update mint price (admin only)
-    pub fn update_mint_price(ctx: Context<UpdateConfig>, new_price: u64) -> Result<()> {
-        let config = &mut ctx.accounts.collection_config;
-        let old_price = config.mint_price_lamports;
-        config.mint_price_lamports = new_price;
+    /// Set or clear a strategy's royalty override and rewrite the royalties on its
+    /// metadata (attestor only). With `None` the NFT follows the collection config
+    /// again, which also pushes `update_royalty_config` changes onto existing NFTs.
+    /// Token-2022 NFTs carry no royalties and are rejected.
+    pub fn update_nft_royalties(
+        ctx: Context<UpdateNftRoyalties>,
+        royalty_override: Option<RoyaltyOverride>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.strategy_nft.token_standard != NftStandard::Token2022,
+            MetaNftError::UnsupportedTokenStandard
+        );
+        if let Some(royalty) = &royalty_override {
+            validate_royalty_config(royalty.seller_fee_basis_points, &royalty.creators)?;
+        }
 
-        emit!(MintPriceUpdated {
-            old_price,
-            new_price,
+        let config = &ctx.accounts.collection_config;
+        let (seller_fee_basis_points, creators) = match &royalty_override {
+            Some(royalty) => (royalty.seller_fee_basis_points, royalty.creators.clone()),
+            None => (config.seller_fee_basis_points, config.creators.clone()),
+        };
+        let metadata = {
+            let data = ctx.accounts.metadata.try_borrow_data()?;
+            MetadataAccount::try_deserialize(&mut &data[..])?
+        };
+        let data = DataV2 {
+            name: metadata.name.trim_end_matches('\0').to_string(),
+            symbol: metadata.symbol.trim_end_matches('\0').to_string(),
+            uri: metadata.uri.trim_end_matches('\0').to_string(),
+            seller_fee_basis_points,
+            creators: Some(to_metadata_creators(&creators, config.key())),
+            collection: metadata.collection.clone(),
+            uses: metadata.uses.clone(),
+        };
+
+        let seeds = &[b"collection".as_ref(), &[config.bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        update_metadata_accounts_v2(
+            CpiContext::new_with_signer(
+                ctx.accounts.metadata_program.to_account_info(),
+                UpdateMetadataAccountsV2 {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    update_authority: config.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            None,
+            Some(data),
+            None,
+            None,
+        )?;
+
+        let is_override = royalty_override.is_some();
+        let strategy_nft = &mut ctx.accounts.strategy_nft;
+        strategy_nft.royalty_override = royalty_override;
+
+        emit!(StrategyRoyaltiesUpdated {
+            mint: strategy_nft.mint,
+            strategy_id: strategy_nft.strategy_id.clone(),
+            seller_fee_basis_points,
+            creators,
+            is_override,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Pause/unpause minting (admin only)
-    pub fn toggle_minting(ctx: Context<UpdateConfig>, is_active: bool) -> Result<()> {
-        let config = &mut ctx.accounts.collection_config;
-        config.is_active = is_active;
+    /// Mark a strategy as killed by evolution, optionally pointing its metadata at
+    /// fossil artwork (attestor only)
+    pub fn record_strategy_death(
+        ctx: Context<RecordStrategyDeath>,
+        fossil_uri: Option<String>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let strategy_nft = &mut ctx.accounts.strategy_nft;
+        require!(
+            strategy_nft.status != StrategyStatus::Dead,
+            MetaNftError::StrategyDead
+        );
+        strategy_nft.status = StrategyStatus::Dead;
+        strategy_nft.died_at = now;
 
-        emit!(MintingToggled {
-            is_active,
-            timestamp: Clock::get()?.unix_timestamp,
+        if let Some(uri) = &fossil_uri {
+            match strategy_nft.token_standard {
+                NftStandard::Token2022 => update_token_metadata_uri(
+                    ctx.accounts.mint.as_ref().ok_or(MetaNftError::MissingMetadata)?,
+                    ctx.accounts.token_program.as_ref().ok_or(MetaNftError::MissingMetadata)?,
+                    &ctx.accounts.attestor,
+                    &ctx.accounts.system_program,
+                    &ctx.accounts.collection_config,
+                    uri.clone(),
+                )?,
+                _ => update_metadata_uri(
+                    &ctx.accounts.metadata_program,
+                    ctx.accounts.metadata.as_ref().ok_or(MetaNftError::MissingMetadata)?,
+                    &ctx.accounts.collection_config,
+                    uri.clone(),
+                )?,
+            }
+        }
+
+        emit!(StrategyDied {
+            mint: strategy_nft.mint,
+            strategy_id: strategy_nft.strategy_id.clone(),
+            generation: strategy_nft.generation,
+            fitness_score: strategy_nft.fitness_score,
+            total_pnl: strategy_nft.total_pnl,
+            win_rate: strategy_nft.win_rate,
+            trades_executed: strategy_nft.trades_executed,
+            sharpe_ratio_milli: strategy_nft.sharpe_ratio_milli,
+            max_drawdown_bps: strategy_nft.max_drawdown_bps,
+            avg_hold_time_secs: strategy_nft.avg_hold_time_secs,
+            fossil_uri,
+            died_at: now,
         });
 
         Ok(())
     }
 
-    /// Transfer authority (admin only)
-    pub fn transfer_authority(ctx: Context<UpdateConfig>, new_authority: Pubkey) -> Result<()> {
+    /// Ask the engine to breed two strategy NFTs (holders of both parents)
+    ///
+    /// The breeding fee is held in an escrow PDA until the request is fulfilled or
+    /// refunded, and both parents are marked as breeding in the meantime. The child
+    /// is minted to `owner_a`, who also pays the fee.
+    pub fn request_breeding(ctx: Context<RequestBreeding>) -> Result<()> {
+        let parent_a = &ctx.accounts.parent_a_nft;
+        let parent_b = &ctx.accounts.parent_b_nft;
+        require!(parent_a.mint != parent_b.mint, MetaNftError::InvalidParent);
+        require!(
+            parent_a.status == StrategyStatus::Active && parent_b.status == StrategyStatus::Active,
+            MetaNftError::ParentNotActive
+        );
+
+        let config = &ctx.accounts.collection_config;
+        let fee = config.breeding_fee_lamports;
+        let request_id = config.total_breeding_requests;
+        let now = Clock::get()?.unix_timestamp;
+
+        if fee > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.owner_a.to_account_info(),
+                        to: ctx.accounts.escrow.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+
+        let request = &mut ctx.accounts.breeding_request;
+        request.id = request_id;
+        request.requester = ctx.accounts.owner_a.key();
+        request.parent_a = parent_a.mint;
+        request.parent_b = parent_b.mint;
+        request.fee = fee;
+        request.created_at = now;
+        request.status = BreedingStatus::Pending;
+        request.child_mint = Pubkey::default();
+        request.bump = ctx.bumps.breeding_request;
+        request.escrow_bump = ctx.bumps.escrow;
+
+        ctx.accounts.parent_a_nft.status = StrategyStatus::Breeding;
+        ctx.accounts.parent_b_nft.status = StrategyStatus::Breeding;
+
         let config = &mut ctx.accounts.collection_config;
-        let old_authority = config.authority;
-        config.authority = new_authority;
+        config.total_breeding_requests = config.total_breeding_requests.checked_add(1).unwrap();
 
-        emit!(AuthorityTransferred {
-            old_authority,
-            new_authority,
-            timestamp: Clock::get()?.unix_timestamp,
+        emit!(BreedingRequested {
+            request: ctx.accounts.breeding_request.key(),
+            request_id,
+            requester: ctx.accounts.owner_a.key(),
+            parent_a: ctx.accounts.parent_a_nft.mint,
+            parent_b: ctx.accounts.parent_b_nft.mint,
+            fee,
+            timestamp: now,
         });
 
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct InitializeCollection<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + CollectionConfig::INIT_SPACE,
-        seeds = [b"collection"],
-        bump
-    )]
-    pub collection_config: Account<'info, CollectionConfig>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    /// Close a breeding request with the child the engine minted to the requester
+    /// and book the escrowed fee as treasury revenue (attestor only)
+    ///
+    /// The child must already be minted through `mint_strategy_nft` with both
+    /// requested parents and still be held by the requester. Each child can fulfil
+    /// one request only, which its `bred_child` PDA records.
+    pub fn fulfil_breeding(ctx: Context<FulfilBreeding>) -> Result<()> {
+        let request = &ctx.accounts.breeding_request;
+        require!(request.status == BreedingStatus::Pending, MetaNftError::BreedingNotPending);
 
-#[derive(Accounts)]
-#[instruction(strategy_id: String)]
-pub struct MintStrategyNft<'info> {
-    #[account(
-        mut,
-        seeds = [b"collection"],
-        bump = collection_config.bump
-    )]
-    pub collection_config: Account<'info, CollectionConfig>,
+        let child = &ctx.accounts.child_nft;
+        require!(
+            child.parent_a == Some(request.parent_a) && child.parent_b == Some(request.parent_b),
+            MetaNftError::InvalidChild
+        );
 
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + StrategyNftData::INIT_SPACE,
-        seeds = [b"strategy_nft", strategy_id.as_bytes()],
-        bump
-    )]
-    pub strategy_nft: Account<'info, StrategyNftData>,
+        let fee = request.fee;
+        if fee > 0 {
+            let request_key = request.key();
+            let seeds = &[b"breeding_escrow".as_ref(), request_key.as_ref(), &[request.escrow_bump]];
+            let signer_seeds = &[&seeds[..]];
 
-    #[account(
-        init,
-        payer = payer,
-        mint::decimals = 0,
-        mint::authority = collection_config,
-        mint::freeze_authority = collection_config,
-    )]
-    pub mint: Account<'info, Mint>,
+            meta_treasury::cpi::deposit_revenue(
+                CpiContext::new_with_signer(
+                    ctx.accounts.treasury_program.to_account_info(),
+                    meta_treasury::cpi::accounts::DepositRevenue {
+                        treasury: ctx.accounts.treasury.to_account_info(),
+                        depositor: ctx.accounts.escrow.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                fee,
+            )?;
+        }
 
-    #[account(
-        init,
-        payer = payer,
-        associated_token::mint = mint,
-        associated_token::authority = payer,
-    )]
-    pub token_account: Account<'info, TokenAccount>,
+        release_parents(&mut ctx.accounts.parent_a_nft, &mut ctx.accounts.parent_b_nft);
 
-    /// CHECK: Metadata account (created via CPI)
-    #[account(mut)]
-    pub metadata: UncheckedAccount<'info>,
+        let request = &mut ctx.accounts.breeding_request;
+        request.status = BreedingStatus::Fulfilled;
+        request.child_mint = ctx.accounts.child_nft.mint;
 
-    /// CHECK: Master edition account (created via CPI)
-    #[account(mut)]
-    pub master_edition: UncheckedAccount<'info>,
+        let bred_child = &mut ctx.accounts.bred_child;
+        bred_child.request_id = request.id;
+        bred_child.bump = ctx.bumps.bred_child;
 
-    /// CHECK: Treasury to receive mint fees
-    #[account(mut)]
-    pub treasury: UncheckedAccount<'info>,
+        emit!(BreedingFulfilled {
+            request: request.key(),
+            request_id: request.id,
+            child_mint: request.child_mint,
+            fee,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-    #[account(mut)]
+        Ok(())
+    }
+
+    /// Return the escrowed fee of a breeding request the engine did not fulfil in
+    /// time (permissionless)
+    pub fn refund_breeding(ctx: Context<RefundBreeding>) -> Result<()> {
+        let request = &ctx.accounts.breeding_request;
+        require!(request.status == BreedingStatus::Pending, MetaNftError::BreedingNotPending);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now >= request
+                .created_at
+                .checked_add(ctx.accounts.collection_config.breeding_timeout_seconds)
+                .unwrap(),
+            MetaNftError::BreedingTimeoutNotReached
+        );
+
+        let fee = request.fee;
+        if fee > 0 {
+            let request_key = request.key();
+            let seeds = &[b"breeding_escrow".as_ref(), request_key.as_ref(), &[request.escrow_bump]];
+            let signer_seeds = &[&seeds[..]];
+
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.escrow.to_account_info(),
+                        to: ctx.accounts.requester.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                fee,
+            )?;
+        }
+
+        release_parents(&mut ctx.accounts.parent_a_nft, &mut ctx.accounts.parent_b_nft);
+
+        let request = &mut ctx.accounts.breeding_request;
+        request.status = BreedingStatus::Refunded;
+
+        emit!(BreedingRefunded {
+            request: request.key(),
+            request_id: request.id,
+            requester: request.requester,
+            fee,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Print the next numbered edition of a strategy NFT to `buyer` (permissionless)
+    ///
+    /// Metaplex requires the master token's owner to sign every print, so the master
+    /// has to sit in the `edition_escrow` PDA (see `escrow_master_edition`), which
+    /// signs for the buyer. The edition price is paid into the `meta_treasury` PDA.
+    /// Only standard NFTs print editions; Token-2022 NFTs are rejected.
+    pub fn mint_edition(ctx: Context<MintEdition>) -> Result<()> {
+        let strategy_nft = &ctx.accounts.strategy_nft;
+        require!(
+            strategy_nft.token_standard == NftStandard::NonFungible,
+            MetaNftError::UnsupportedTokenStandard
+        );
+        require!(
+            strategy_nft.editions_printed < strategy_nft.max_editions,
+            MetaNftError::EditionSupplyExhausted
+        );
+        let edition = strategy_nft.editions_printed.checked_add(1).unwrap();
+        let price = ctx.accounts.collection_config.edition_price_lamports;
+
+        if price > 0 {
+            meta_treasury::cpi::deposit_revenue(
+                CpiContext::new(
+                    ctx.accounts.treasury_program.to_account_info(),
+                    meta_treasury::cpi::accounts::DepositRevenue {
+                        treasury: ctx.accounts.treasury.to_account_info(),
+                        depositor: ctx.accounts.buyer.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                    },
+                ),
+                price,
+            )?;
+        }
+
+        let seeds = &[b"collection".as_ref(), &[ctx.accounts.collection_config.bump]];
+        let master_mint = ctx.accounts.master_mint.key();
+        let escrow_seeds = &[
+            b"edition_escrow".as_ref(),
+            master_mint.as_ref(),
+            &[ctx.accounts.edition_escrow.bump],
+        ];
+        let signer_seeds = &[&seeds[..], &escrow_seeds[..]];
+
+        // Metaplex expects the edition mint to already hold its single token
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.edition_mint.to_account_info(),
+                    to: ctx.accounts.edition_token_account.to_account_info(),
+                    authority: ctx.accounts.collection_config.to_account_info(),
+                },
+                &signer_seeds[..1],
+            ),
+            1,
+        )?;
+
+        mint_new_edition_from_master_edition_via_token(
+            CpiContext::new_with_signer(
+                ctx.accounts.metadata_program.to_account_info(),
+                MintNewEditionFromMasterEditionViaToken {
+                    new_metadata: ctx.accounts.edition_metadata.to_account_info(),
+                    new_edition: ctx.accounts.edition.to_account_info(),
+                    master_edition: ctx.accounts.master_edition.to_account_info(),
+                    new_mint: ctx.accounts.edition_mint.to_account_info(),
+                    edition_mark_pda: ctx.accounts.edition_mark.to_account_info(),
+                    new_mint_authority: ctx.accounts.collection_config.to_account_info(),
+                    payer: ctx.accounts.buyer.to_account_info(),
+                    token_account_owner: ctx.accounts.edition_escrow.to_account_info(),
+                    token_account: ctx.accounts.master_token_account.to_account_info(),
+                    new_metadata_update_authority: ctx.accounts.collection_config.to_account_info(),
+                    metadata: ctx.accounts.master_metadata.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                    metadata_mint: ctx.accounts.master_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            edition,
+        )?;
+
+        let strategy_nft = &mut ctx.accounts.strategy_nft;
+        strategy_nft.editions_printed = edition;
+
+        emit!(EditionMinted {
+            master_mint: strategy_nft.mint,
+            edition_mint: ctx.accounts.edition_mint.key(),
+            strategy_id: strategy_nft.strategy_id.clone(),
+            edition,
+            buyer: ctx.accounts.buyer.key(),
+            price,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Move a master NFT into the `edition_escrow` PDA so anyone can print its
+    /// editions through `mint_edition` (holder only)
+    ///
+    /// Only standard NFTs print editions. The NFT stays in escrow until the depositor
+    /// takes it back with `release_master_edition`.
+    pub fn escrow_master_edition(ctx: Context<EscrowMasterEdition>) -> Result<()> {
+        let strategy_nft = &ctx.accounts.strategy_nft;
+        require!(
+            strategy_nft.token_standard == NftStandard::NonFungible,
+            MetaNftError::UnsupportedTokenStandard
+        );
+        require!(
+            strategy_nft.editions_printed < strategy_nft.max_editions,
+            MetaNftError::EditionSupplyExhausted
+        );
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.holder_token_account.to_account_info(),
+                    to: ctx.accounts.escrow_token_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+
+        let escrow = &mut ctx.accounts.edition_escrow;
+        escrow.mint = strategy_nft.mint;
+        escrow.depositor = ctx.accounts.holder.key();
+        escrow.bump = ctx.bumps.edition_escrow;
+
+        emit!(MasterEditionEscrowed {
+            mint: strategy_nft.mint,
+            strategy_id: strategy_nft.strategy_id.clone(),
+            depositor: escrow.depositor,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Return an escrowed master NFT to its depositor, ending open edition printing
+    /// (depositor only)
+    ///
+    /// The escrow's token account and state are closed with their rent refunded.
+    pub fn release_master_edition(ctx: Context<ReleaseMasterEdition>) -> Result<()> {
+        let mint = ctx.accounts.edition_escrow.mint;
+        let seeds = &[
+            b"edition_escrow".as_ref(),
+            mint.as_ref(),
+            &[ctx.accounts.edition_escrow.bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow_token_account.to_account_info(),
+                    to: ctx.accounts.depositor_token_account.to_account_info(),
+                    authority: ctx.accounts.edition_escrow.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            1,
+        )?;
+
+        close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_token_account.to_account_info(),
+                destination: ctx.accounts.depositor.to_account_info(),
+                authority: ctx.accounts.edition_escrow.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        emit!(MasterEditionReleased {
+            mint,
+            strategy_id: ctx.accounts.strategy_nft.strategy_id.clone(),
+            depositor: ctx.accounts.depositor.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Burn a strategy NFT and close its data and history accounts (holder only)
+    ///
+    /// Metaplex closes the token, metadata and edition accounts and the NFT leaves the
+    /// sized collection, if it is in one. Token-2022 NFTs are burned and their token account closed
+    /// directly. All reclaimed rent goes to the holder.
+    pub fn burn_strategy_nft(ctx: Context<BurnStrategyNft>) -> Result<()> {
+        // An open breeding request still needs the parent's data to close
+        require!(
+            ctx.accounts.strategy_nft.status != StrategyStatus::Breeding,
+            MetaNftError::StrategyBreeding
+        );
+
+        match ctx.accounts.strategy_nft.token_standard {
+            NftStandard::NonFungible => {
+                let cpi_ctx = CpiContext::new(
+                    ctx.accounts.metadata_program.to_account_info(),
+                    BurnNft {
+                        metadata: ctx.accounts.metadata.to_account_info(),
+                        owner: ctx.accounts.owner.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        token: ctx.accounts.token_account.to_account_info(),
+                        edition: ctx.accounts.master_edition.to_account_info(),
+                        spl_token: ctx.accounts.token_program.to_account_info(),
+                    },
+                );
+
+                // NFTs minted before the collection existed and later migrated have none
+                let in_collection = {
+                    let data = ctx.accounts.metadata.try_borrow_data()?;
+                    MetadataAccount::try_deserialize(&mut &data[..])?
+                        .collection
+                        .as_ref()
+                        .is_some_and(|collection| collection.verified)
+                };
+                if in_collection {
+                    burn_nft(
+                        cpi_ctx.with_remaining_accounts(vec![ctx
+                            .accounts
+                            .collection_metadata
+                            .to_account_info()]),
+                        Some(ctx.accounts.collection_metadata.key()),
+                    )?
+                } else {
+                    burn_nft(cpi_ctx, None)?
+                }
+            }
+            // pNFT token accounts are frozen, so only BurnV1 can burn them
+            NftStandard::ProgrammableNonFungible => {
+                let token_record = ctx
+                    .accounts
+                    .token_record
+                    .as_ref()
+                    .ok_or(MetaNftError::MissingTokenRecord)?
+                    .to_account_info();
+                let collection_metadata = ctx.accounts.collection_metadata.to_account_info();
+                let master_edition = ctx.accounts.master_edition.to_account_info();
+
+                BurnV1CpiBuilder::new(&ctx.accounts.metadata_program.to_account_info())
+                    .authority(&ctx.accounts.owner.to_account_info())
+                    .collection_metadata(Some(&collection_metadata))
+                    .metadata(&ctx.accounts.metadata.to_account_info())
+                    .edition(Some(&master_edition))
+                    .mint(&ctx.accounts.mint.to_account_info())
+                    .token(&ctx.accounts.token_account.to_account_info())
+                    .token_record(Some(&token_record))
+                    .system_program(&ctx.accounts.system_program.to_account_info())
+                    .sysvar_instructions(&ctx.accounts.instructions_sysvar.to_account_info())
+                    .spl_token_program(&ctx.accounts.token_program.to_account_info())
+                    .amount(1)
+                    .invoke()?;
+            }
+            // No Metaplex accounts to close, so burn the token and close its account directly
+            NftStandard::Token2022 => {
+                let token_program = ctx.accounts.token_program.to_account_info();
+                token_interface::burn(
+                    CpiContext::new(
+                        token_program.clone(),
+                        token_interface::Burn {
+                            mint: ctx.accounts.mint.to_account_info(),
+                            from: ctx.accounts.token_account.to_account_info(),
+                            authority: ctx.accounts.owner.to_account_info(),
+                        },
+                    ),
+                    1,
+                )?;
+                token_interface::close_account(CpiContext::new(
+                    token_program,
+                    token_interface::CloseAccount {
+                        account: ctx.accounts.token_account.to_account_info(),
+                        destination: ctx.accounts.owner.to_account_info(),
+                        authority: ctx.accounts.owner.to_account_info(),
+                    },
+                ))?;
+            }
+        }
+
+        let config = &mut ctx.accounts.collection_config;
+        config.total_burned = config.total_burned.checked_add(1).unwrap();
+
+        emit!(StrategyNftBurned {
+            mint: ctx.accounts.mint.key(),
+            strategy_id: ctx.accounts.strategy_nft.strategy_id.clone(),
+            owner: ctx.accounts.owner.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Move a strategy NFT to another wallet and update `owner` (holder only)
+    ///
+    /// Metaplex NFTs go through `TransferV1`, which handles both standards and makes
+    /// pNFT transfers pass the rule set stored on their metadata. Token-2022 NFTs have
+    /// no Metaplex accounts and move with `transfer_checked`.
+    pub fn transfer_strategy_nft(ctx: Context<TransferStrategyNft>) -> Result<()> {
+        if ctx.accounts.strategy_nft.token_standard == NftStandard::Token2022 {
+            transfer_token_2022_nft(ctx.accounts)?;
+        } else {
+            transfer_metaplex_nft(ctx.accounts)?;
+        }
+
+        let new_owner = ctx.accounts.destination_owner.key();
+        let strategy_nft = &mut ctx.accounts.strategy_nft;
+        let old_owner = strategy_nft.owner;
+        strategy_nft.owner = new_owner;
+
+        emit!(StrategyOwnerChanged {
+            mint: strategy_nft.mint,
+            strategy_id: strategy_nft.strategy_id.clone(),
+            old_owner,
+            new_owner,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Point `owner` at the wallet currently holding the NFT (permissionless)
+    pub fn sync_owner(ctx: Context<SyncOwner>) -> Result<()> {
+        let new_owner = ctx.accounts.token_account.owner;
+        let strategy_nft = &mut ctx.accounts.strategy_nft;
+        let old_owner = strategy_nft.owner;
+        if old_owner == new_owner {
+            return Ok(());
+        }
+        strategy_nft.owner = new_owner;
+
+        emit!(StrategyOwnerChanged {
+            mint: strategy_nft.mint,
+            strategy_id: strategy_nft.strategy_id.clone(),
+            old_owner,
+            new_owner,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Grow a collection config created under the legacy layout to the current one and
+    /// mint the collection NFT it now owns (admin only)
+    ///
+    /// The legacy config only held the authority, counters, mint price and active flag,
+    /// which are kept. Branding comes from the arguments, and the other fields get the
+    /// defaults `initialize_collection` uses. No attestor is set, so minting stays
+    /// blocked until `set_attestor`.
+    pub fn migrate_collection_config(
+        ctx: Context<MigrateCollectionConfig>,
+        name: String,
+        symbol: String,
+        uri: String,
+        name_prefix: String,
+        base_uri: String,
+    ) -> Result<()> {
+        validate_metadata_config(&name_prefix, &symbol, &base_uri)?;
+
+        let info = ctx.accounts.collection_config.to_account_info();
+        require_keys_eq!(*info.owner, crate::ID, MetaNftError::InvalidCollection);
+        let legacy = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == <CollectionConfig as Discriminator>::DISCRIMINATOR,
+                MetaNftError::InvalidCollection
+            );
+            require!(
+                data.len() == 8 + LegacyCollectionConfig::INIT_SPACE,
+                MetaNftError::AlreadyMigrated
+            );
+            LegacyCollectionConfig::deserialize(&mut &data[8..])?
+        };
+        require_keys_eq!(legacy.authority, ctx.accounts.authority.key(), MetaNftError::Unauthorized);
+
+        let new_len = 8 + CollectionConfig::INIT_SPACE;
+        fund_rent(&info, new_len, &ctx.accounts.authority, &ctx.accounts.system_program)?;
+        info.realloc(new_len, false)?;
+
+        let config_key = info.key();
+        let config = CollectionConfig {
+            authority: legacy.authority,
+            total_minted: legacy.total_minted,
+            mint_price_lamports: legacy.mint_price_lamports,
+            is_active: legacy.is_active,
+            bump: legacy.bump,
+            collection_mint: ctx.accounts.collection_mint.key(),
+            name_prefix,
+            symbol: symbol.clone(),
+            base_uri,
+            seller_fee_basis_points: 500, // 5% royalty, as legacy NFTs were minted with
+            creators: vec![CreatorShare {
+                address: config_key,
+                share: 100,
+            }],
+            attestor: Pubkey::default(),
+            total_burned: 0,
+            breeding_fee_lamports: 50_000_000, // 0.05 SOL default
+            breeding_timeout_seconds: DEFAULT_BREEDING_TIMEOUT_SECONDS,
+            total_breeding_requests: 0,
+            edition_price_lamports: 20_000_000, // 0.02 SOL default
+            token_standard: NftStandard::NonFungible,
+            rule_set: None,
+        };
+        config.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        mint_collection_nft(
+            &info,
+            legacy.bump,
+            &ctx.accounts.collection_mint,
+            &ctx.accounts.collection_token_account,
+            &ctx.accounts.collection_metadata,
+            &ctx.accounts.collection_master_edition,
+            &ctx.accounts.authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.metadata_program,
+            &ctx.accounts.system_program,
+            &ctx.accounts.rent,
+            DataV2 {
+                name,
+                symbol,
+                uri,
+                seller_fee_basis_points: config.seller_fee_basis_points,
+                creators: Some(config.metadata_creators(config_key)),
+                collection: None,
+                uses: None,
+            },
+        )?;
+
+        emit!(CollectionConfigMigrated {
+            authority: config.authority,
+            collection_mint: config.collection_mint,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Rewrite a strategy NFT account created before the extended performance
+    /// fields into the current layout and create its history and genes registry
+    /// accounts (admin only)
+    ///
+    /// Legacy `win_rate` values were already basis points (6800 is 68.00%), so they are
+    /// copied as is and out-of-range values are rejected. The new fields start at zero
+    /// until the next stats update. The legacy hex `genes_hash` must decode to `genes_hash`.
+    /// The collection config must be migrated first with `migrate_collection_config`.
+    pub fn migrate_strategy_nft(
+        ctx: Context<MigrateStrategyNft>,
+        _strategy_id: String,
+        genes_hash: [u8; 32],
+    ) -> Result<()> {
+        let info = ctx.accounts.strategy_nft.to_account_info();
+        require_keys_eq!(*info.owner, crate::ID, MetaNftError::InvalidStrategyData);
+        require!(
+            info.data_len() == 8 + LegacyStrategyNftData::INIT_SPACE,
+            MetaNftError::AlreadyMigrated
+        );
+
+        let legacy = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data[..8] == <StrategyNftData as Discriminator>::DISCRIMINATOR,
+                MetaNftError::InvalidStrategyData
+            );
+            LegacyStrategyNftData::deserialize(&mut &data[8..])?
+        };
+        require_keys_eq!(legacy.mint, ctx.accounts.mint.key(), MetaNftError::InvalidStrategyData);
+        require!(
+            decode_genes_hash(&legacy.genes_hash) == Some(genes_hash),
+            MetaNftError::InvalidStrategyData
+        );
+        require!(legacy.win_rate <= MAX_BPS, MetaNftError::InvalidStrategyData);
+
+        // Top up rent if the new layout needs more than the account holds
+        let new_len = 8 + StrategyNftData::INIT_SPACE;
+        let shortfall = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(info.lamports());
+        if shortfall > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: info.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        info.realloc(new_len, false)?;
+
+        let migrated = StrategyNftData {
+            mint: legacy.mint,
+            strategy_id: legacy.strategy_id,
+            genes_hash,
+            owner: legacy.owner,
+            minted_at: legacy.minted_at,
+            mint_price: legacy.mint_price,
+            archetype: legacy.archetype,
+            generation: legacy.generation,
+            fitness_score: legacy.fitness_score,
+            total_pnl: legacy.total_pnl,
+            win_rate: legacy.win_rate,
+            trades_executed: legacy.trades_executed,
+            sharpe_ratio_milli: 0,
+            max_drawdown_bps: 0,
+            avg_hold_time_secs: 0,
+            bump: legacy.bump,
+            last_updated: legacy.minted_at,
+            status: StrategyStatus::Active,
+            died_at: 0,
+            parent_a: None,
+            parent_b: None,
+            max_editions: 0,
+            editions_printed: 0,
+            royalty_override: None,
+            token_standard: NftStandard::NonFungible,
+        };
+        migrated.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        let history = &mut ctx.accounts.performance_history;
+        history.mint = migrated.mint;
+        history.bump = ctx.bumps.performance_history;
+        history.push(migrated.snapshot());
+
+        let registry = &mut ctx.accounts.genes_registry;
+        registry.genes_hash = genes_hash;
+        registry.mint = migrated.mint;
+        registry.bump = ctx.bumps.genes_registry;
+
+        emit!(StrategyNftMigrated {
+            mint: migrated.mint,
+            strategy_id: migrated.strategy_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Update mint price (admin only)
+    pub fn update_mint_price(ctx: Context<UpdateConfig>, new_price: u64) -> Result<()> {
+        let config = &mut ctx.accounts.collection_config;
+        let old_price = config.mint_price_lamports;
+        config.mint_price_lamports = new_price;
+
+        emit!(MintPriceUpdated {
+            old_price,
+            new_price,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Pause/unpause minting (admin only)
+    pub fn toggle_minting(ctx: Context<UpdateConfig>, is_active: bool) -> Result<()> {
+        let config = &mut ctx.accounts.collection_config;
+        config.is_active = is_active;
+
+        emit!(MintingToggled {
+            is_active,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Update the name prefix, symbol and base URI used for new mints (admin only)
+    pub fn update_metadata_config(
+        ctx: Context<UpdateConfig>,
+        name_prefix: String,
+        symbol: String,
+        base_uri: String,
+    ) -> Result<()> {
+        validate_metadata_config(&name_prefix, &symbol, &base_uri)?;
+
+        let config = &mut ctx.accounts.collection_config;
+        config.name_prefix = name_prefix.clone();
+        config.symbol = symbol.clone();
+        config.base_uri = base_uri.clone();
+
+        emit!(MetadataConfigUpdated {
+            name_prefix,
+            symbol,
+            base_uri,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Update the royalty and creator split used for new mints (admin only)
+    pub fn update_royalty_config(
+        ctx: Context<UpdateConfig>,
+        seller_fee_basis_points: u16,
+        creators: Vec<CreatorShare>,
+    ) -> Result<()> {
+        validate_royalty_config(seller_fee_basis_points, &creators)?;
+
+        let config = &mut ctx.accounts.collection_config;
+        config.seller_fee_basis_points = seller_fee_basis_points;
+        config.creators = creators.clone();
+
+        emit!(RoyaltyConfigUpdated {
+            seller_fee_basis_points,
+            creators,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set how many editions a not yet minted strategy can print (admin only)
+    ///
+    /// Metaplex fixes the supply when the master edition is created, so this has to
+    /// happen before `mint_strategy_nft`.
+    pub fn set_edition_supply(
+        ctx: Context<SetEditionSupply>,
+        strategy_id: String,
+        max_supply: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.strategy_nft.data_is_empty(),
+            MetaNftError::EditionSupplyLocked
+        );
+
+        let edition_supply = &mut ctx.accounts.edition_supply;
+        edition_supply.max_supply = max_supply;
+        edition_supply.bump = ctx.bumps.edition_supply;
+
+        emit!(EditionSupplySet {
+            strategy_id,
+            max_supply,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Update the price of printed editions (admin only)
+    pub fn update_edition_price(ctx: Context<UpdateConfig>, new_price: u64) -> Result<()> {
+        let config = &mut ctx.accounts.collection_config;
+        let old_price = config.edition_price_lamports;
+        config.edition_price_lamports = new_price;
+
+        emit!(EditionPriceUpdated {
+            old_price,
+            new_price,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Choose the token standard for new mints and, for pNFTs, the Metaplex rule set
+    /// that enforces royalties on transfers (admin only)
+    pub fn update_token_standard(
+        ctx: Context<UpdateConfig>,
+        token_standard: NftStandard,
+        rule_set: Option<Pubkey>,
+    ) -> Result<()> {
+        require!(
+            rule_set.is_none() || token_standard == NftStandard::ProgrammableNonFungible,
+            MetaNftError::InvalidRuleSet
+        );
+
+        let config = &mut ctx.accounts.collection_config;
+        config.token_standard = token_standard;
+        config.rule_set = rule_set;
+
+        emit!(TokenStandardUpdated {
+            token_standard,
+            rule_set,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Update the breeding fee and how long the engine has to fulfil requests (admin only)
+    pub fn update_breeding_config(
+        ctx: Context<UpdateConfig>,
+        breeding_fee_lamports: u64,
+        breeding_timeout_seconds: i64,
+    ) -> Result<()> {
+        require!(breeding_timeout_seconds > 0, MetaNftError::InvalidBreedingConfig);
+        // The escrow is a plain system account, so a fee has to make it rent-exempt
+        require!(
+            breeding_fee_lamports == 0
+                || breeding_fee_lamports >= Rent::get()?.minimum_balance(0),
+            MetaNftError::InvalidBreedingConfig
+        );
+
+        let config = &mut ctx.accounts.collection_config;
+        config.breeding_fee_lamports = breeding_fee_lamports;
+        config.breeding_timeout_seconds = breeding_timeout_seconds;
+
+        emit!(BreedingConfigUpdated {
+            breeding_fee_lamports,
+            breeding_timeout_seconds,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set the engine key that signs mint attestations (admin only)
+    pub fn set_attestor(ctx: Context<UpdateConfig>, attestor: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.collection_config;
+        let old_attestor = config.attestor;
+        config.attestor = attestor;
+
+        emit!(AttestorUpdated {
+            old_attestor,
+            new_attestor: attestor,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Transfer authority (admin only)
+    pub fn transfer_authority(ctx: Context<UpdateConfig>, new_authority: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.collection_config;
+        let old_authority = config.authority;
+        config.authority = new_authority;
+
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+fn validate_metadata_config(name_prefix: &str, symbol: &str, base_uri: &str) -> Result<()> {
+    require!(
+        !name_prefix.is_empty() && name_prefix.len() <= MAX_NAME_PREFIX_LEN,
+        MetaNftError::InvalidMetadataConfig
+    );
+    require!(
+        !symbol.is_empty() && symbol.len() <= MAX_SYMBOL_LEN,
+        MetaNftError::InvalidMetadataConfig
+    );
+    require!(base_uri.len() <= MAX_BASE_URI_LEN, MetaNftError::InvalidMetadataConfig);
+    Ok(())
+}
+
+fn validate_performance(win_rate: u64, sharpe_ratio_milli: i32, max_drawdown_bps: u16) -> Result<()> {
+    require!(win_rate <= MAX_BPS, MetaNftError::InvalidStrategyData);
+    require!(
+        (-MAX_SHARPE_RATIO_MILLI..=MAX_SHARPE_RATIO_MILLI).contains(&sharpe_ratio_milli),
+        MetaNftError::InvalidStrategyData
+    );
+    require!(max_drawdown_bps as u64 <= MAX_BPS, MetaNftError::InvalidStrategyData);
+    Ok(())
+}
+
+/// Decode a legacy hex `genes_hash` string into its 32 raw bytes
+fn decode_genes_hash(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+/// Checks shared by every mint path: minting is open, the stats are in range, published
+/// genes and parents match the attestation, and the attestor signed it in time
+fn verify_mint_request(
+    config: &CollectionConfig,
+    instructions_sysvar: &AccountInfo,
+    attestation: &MintAttestation,
+    genes: &Option<StrategyGenes>,
+    has_genes_account: bool,
+    parent_a_nft: &Option<Box<Account<StrategyNftData>>>,
+    parent_b_nft: &Option<Box<Account<StrategyNftData>>>,
+) -> Result<()> {
+    require!(config.is_active, MetaNftError::MintingPaused);
+    validate_performance(
+        attestation.win_rate,
+        attestation.sharpe_ratio_milli,
+        attestation.max_drawdown_bps,
+    )?;
+    require!(has_genes_account == genes.is_some(), MetaNftError::InvalidStrategyData);
+    // Published genes must hash to the attested genes_hash
+    if let Some(genes) = genes {
+        require!(
+            hash(&genes.try_to_vec()?).to_bytes() == attestation.genes_hash,
+            MetaNftError::GenesHashMismatch
+        );
+    }
+    validate_parent(attestation.parent_a, parent_a_nft)?;
+    validate_parent(attestation.parent_b, parent_b_nft)?;
+    if let Some(royalty) = &attestation.royalty_override {
+        validate_royalty_config(royalty.seller_fee_basis_points, &royalty.creators)?;
+    }
+    require!(config.attestor != Pubkey::default(), MetaNftError::AttestorNotSet);
+    require!(
+        Clock::get()?.unix_timestamp <= attestation.expires_at,
+        MetaNftError::AttestationExpired
+    );
+
+    verify_mint_attestation(instructions_sysvar, &config.attestor, attestation)
+}
+
+/// Pay a mint fee into the treasury through `deposit_revenue` so it is booked as revenue
+fn deposit_mint_fee<'info>(
+    treasury_program: &Program<'info, MetaTreasury>,
+    treasury: &UncheckedAccount<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    meta_treasury::cpi::deposit_revenue(
+        CpiContext::new(
+            treasury_program.to_account_info(),
+            meta_treasury::cpi::accounts::DepositRevenue {
+                treasury: treasury.to_account_info(),
+                depositor: payer.to_account_info(),
+                system_program: system_program.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+/// Strategy stats a Token-2022 NFT exposes as additional token-metadata fields
+fn token_metadata_fields(archetype: &str, generation: u32, fitness_score: u64) -> Vec<(String, String)> {
+    vec![
+        (
+            "fitness".to_string(),
+            format!("{}.{:02}", fitness_score / 100, fitness_score % 100),
+        ),
+        ("generation".to_string(), generation.to_string()),
+        ("archetype".to_string(), archetype.to_string()),
+    ]
+}
+
+/// Mint the sized collection NFT into the config PDA's token account, with the config
+/// as its update authority
+#[allow(clippy::too_many_arguments)]
+fn mint_collection_nft<'info>(
+    collection_config: &AccountInfo<'info>,
+    config_bump: u8,
+    collection_mint: &Account<'info, Mint>,
+    collection_token_account: &Account<'info, TokenAccount>,
+    collection_metadata: &UncheckedAccount<'info>,
+    collection_master_edition: &UncheckedAccount<'info>,
+    payer: &Signer<'info>,
+    token_program: &Program<'info, Token>,
+    metadata_program: &Program<'info, Metadata>,
+    system_program: &Program<'info, System>,
+    rent: &Sysvar<'info, Rent>,
+    data: DataV2,
+) -> Result<()> {
+    let seeds = &[b"collection".as_ref(), &[config_bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    // Collection NFT token is held by the config PDA
+    mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: collection_mint.to_account_info(),
+                to: collection_token_account.to_account_info(),
+                authority: collection_config.clone(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: collection_metadata.to_account_info(),
+                mint: collection_mint.to_account_info(),
+                mint_authority: collection_config.clone(),
+                payer: payer.to_account_info(),
+                update_authority: collection_config.clone(),
+                system_program: system_program.to_account_info(),
+                rent: rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        data,
+        true,
+        true,
+        Some(CollectionDetails::V1 { size: 0 }), // Sized collection, counted by Metaplex on verify
+    )?;
+
+    create_master_edition_v3(
+        CpiContext::new_with_signer(
+            metadata_program.to_account_info(),
+            CreateMasterEditionV3 {
+                edition: collection_master_edition.to_account_info(),
+                mint: collection_mint.to_account_info(),
+                update_authority: collection_config.clone(),
+                mint_authority: collection_config.clone(),
+                payer: payer.to_account_info(),
+                metadata: collection_metadata.to_account_info(),
+                token_program: token_program.to_account_info(),
+                system_program: system_program.to_account_info(),
+                rent: rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        Some(0),
+    )
+}
+
+/// Move enough lamports from `payer` for `account` to stay rent exempt at `new_len` bytes
+fn fund_rent<'info>(
+    account: &AccountInfo<'info>,
+    new_len: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let shortfall = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    Ok(())
+}
+
+/// A parent mint argument and its `StrategyNftData` account must be given together
+fn validate_parent(
+    parent_mint: Option<Pubkey>,
+    parent_nft: &Option<Box<Account<StrategyNftData>>>,
+) -> Result<()> {
+    match (parent_mint, parent_nft) {
+        (None, None) => Ok(()),
+        (Some(mint), Some(nft)) if nft.mint == mint => Ok(()),
+        _ => err!(MetaNftError::InvalidParent),
+    }
+}
+
+/// Edition supply the admin configured for a strategy, 0 (a strict 1/1) if none
+fn read_edition_supply(edition_supply: &AccountInfo) -> Result<u64> {
+    if edition_supply.owner != &crate::ID || edition_supply.data_is_empty() {
+        return Ok(0);
+    }
+    Ok(EditionSupply::try_deserialize(&mut &edition_supply.try_borrow_data()?[..])?.max_supply)
+}
+
+/// Create, mint and verify a strategy NFT as a pNFT through the Metaplex `CreateV1`,
+/// `MintV1` and `VerifyCollectionV1` builders, recording the collection's rule set
+fn mint_programmable_nft(
+    accounts: &MintStrategyNft,
+    data: DataV2,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let config = &accounts.collection_config;
+    require!(
+        accounts.authorization_rules.as_ref().map(|a| a.key()) == config.rule_set,
+        MetaNftError::InvalidRuleSet
+    );
+    let token_record = accounts
+        .token_record
+        .as_ref()
+        .ok_or(MetaNftError::MissingTokenRecord)?
+        .to_account_info();
+    let authorization_rules = accounts.authorization_rules.as_ref().map(|a| a.to_account_info());
+    let authorization_rules_program = accounts
+        .authorization_rules_program
+        .as_ref()
+        .map(|a| a.to_account_info());
+
+    let metadata_program = accounts.metadata_program.to_account_info();
+    let metadata = accounts.metadata.to_account_info();
+    let master_edition = accounts.master_edition.to_account_info();
+    let mint = accounts.mint.to_account_info();
+    let authority = config.to_account_info();
+    let payer = accounts.payer.to_account_info();
+    let system_program = accounts.system_program.to_account_info();
+    let sysvar_instructions = accounts.instructions_sysvar.to_account_info();
+    let token_program = accounts.token_program.to_account_info();
+
+    let mut create = CreateV1CpiBuilder::new(&metadata_program);
+    create
+        .metadata(&metadata)
+        .master_edition(Some(&master_edition))
+        .mint(&mint, false)
+        .authority(&authority)
+        .payer(&payer)
+        .update_authority(&authority, true)
+        .system_program(&system_program)
+        .sysvar_instructions(&sysvar_instructions)
+        .spl_token_program(Some(&token_program))
+        .name(data.name)
+        .symbol(data.symbol)
+        .uri(data.uri)
+        .seller_fee_basis_points(data.seller_fee_basis_points)
+        .creators(data.creators.unwrap_or_default())
+        .primary_sale_happened(false)
+        .is_mutable(true)
+        .token_standard(TokenStandard::ProgrammableNonFungible)
+        .print_supply(PrintSupply::Zero);
+    if let Some(collection) = data.collection {
+        create.collection(collection);
+    }
+    if let Some(rule_set) = config.rule_set {
+        create.rule_set(rule_set);
+    }
+    create.invoke_signed(signer_seeds)?;
+
+    // MintV1 also creates the token record and freezes the recipient's token account
+    MintV1CpiBuilder::new(&metadata_program)
+        .token(&accounts.token_account.to_account_info())
+        .token_owner(Some(&accounts.recipient.to_account_info()))
+        .metadata(&metadata)
+        .master_edition(Some(&master_edition))
+        .token_record(Some(&token_record))
+        .mint(&mint)
+        .authority(&authority)
+        .payer(&payer)
+        .system_program(&system_program)
+        .sysvar_instructions(&sysvar_instructions)
+        .spl_token_program(&token_program)
+        .spl_ata_program(&accounts.associated_token_program.to_account_info())
+        .authorization_rules_program(authorization_rules_program.as_ref())
+        .authorization_rules(authorization_rules.as_ref())
+        .amount(1)
+        .invoke_signed(signer_seeds)?;
+
+    let collection_metadata = accounts.collection_metadata.to_account_info();
+    let collection_master_edition = accounts.collection_master_edition.to_account_info();
+    VerifyCollectionV1CpiBuilder::new(&metadata_program)
+        .authority(&authority)
+        .metadata(&metadata)
+        .collection_mint(&accounts.collection_mint.to_account_info())
+        .collection_metadata(Some(&collection_metadata))
+        .collection_master_edition(Some(&collection_master_edition))
+        .system_program(&system_program)
+        .sysvar_instructions(&sysvar_instructions)
+        .invoke_signed(signer_seeds)?;
+
+    Ok(())
+}
+
+/// Put parents back to active once their breeding request closes, unless they died meanwhile
+fn release_parents(parent_a: &mut StrategyNftData, parent_b: &mut StrategyNftData) {
+    for parent in [parent_a, parent_b] {
+        if parent.status == StrategyStatus::Breeding {
+            parent.status = StrategyStatus::Active;
+        }
+    }
+}
+
+fn validate_royalty_config(seller_fee_basis_points: u16, creators: &[CreatorShare]) -> Result<()> {
+    require!(
+        seller_fee_basis_points <= MAX_SELLER_FEE_BASIS_POINTS,
+        MetaNftError::InvalidRoyaltyConfig
+    );
+    require!(
+        !creators.is_empty() && creators.len() <= MAX_CREATORS,
+        MetaNftError::InvalidRoyaltyConfig
+    );
+    require!(
+        creators.iter().map(|c| c.share as u16).sum::<u16>() == 100,
+        MetaNftError::InvalidRoyaltyConfig
+    );
+    for (i, creator) in creators.iter().enumerate() {
+        require!(
+            !creators[..i].iter().any(|c| c.address == creator.address),
+            MetaNftError::InvalidRoyaltyConfig
+        );
+    }
+    Ok(())
+}
+
+/// Move a Metaplex strategy NFT through `TransferV1`, which also creates the receiver's
+/// token account and makes pNFT transfers pass the rule set stored on their metadata
+fn transfer_metaplex_nft(accounts: &TransferStrategyNft) -> Result<()> {
+    let programmable = accounts.strategy_nft.token_standard == NftStandard::ProgrammableNonFungible;
+    let token_record = accounts.token_record.as_ref().map(|a| a.to_account_info());
+    let destination_token_record = accounts
+        .destination_token_record
+        .as_ref()
+        .map(|a| a.to_account_info());
+    require!(
+        !programmable || (token_record.is_some() && destination_token_record.is_some()),
+        MetaNftError::MissingTokenRecord
+    );
+    let authorization_rules = accounts.authorization_rules.as_ref().map(|a| a.to_account_info());
+    let authorization_rules_program = accounts
+        .authorization_rules_program
+        .as_ref()
+        .map(|a| a.to_account_info());
+    let master_edition = accounts.master_edition.to_account_info();
+    let owner = accounts.owner.to_account_info();
+
+    TransferV1CpiBuilder::new(&accounts.metadata_program.to_account_info())
+        .token(&accounts.token_account.to_account_info())
+        .token_owner(&owner)
+        .destination_token(&accounts.destination_token.to_account_info())
+        .destination_owner(&accounts.destination_owner.to_account_info())
+        .mint(&accounts.mint.to_account_info())
+        .metadata(&accounts.metadata.to_account_info())
+        .edition(Some(&master_edition))
+        .token_record(token_record.as_ref())
+        .destination_token_record(destination_token_record.as_ref())
+        .authority(&owner)
+        .payer(&owner)
+        .system_program(&accounts.system_program.to_account_info())
+        .sysvar_instructions(&accounts.instructions_sysvar.to_account_info())
+        .spl_token_program(&accounts.token_program.to_account_info())
+        .spl_ata_program(&accounts.associated_token_program.to_account_info())
+        .authorization_rules_program(authorization_rules_program.as_ref())
+        .authorization_rules(authorization_rules.as_ref())
+        .amount(1)
+        .invoke()?;
+
+    Ok(())
+}
+
+/// Move a Token-2022 strategy NFT with `transfer_checked`, creating the receiver's
+/// associated token account if needed
+fn transfer_token_2022_nft(accounts: &TransferStrategyNft) -> Result<()> {
+    let token_program = accounts.token_program.to_account_info();
+
+    create_idempotent(CpiContext::new(
+        accounts.associated_token_program.to_account_info(),
+        Create {
+            payer: accounts.owner.to_account_info(),
+            associated_token: accounts.destination_token.to_account_info(),
+            authority: accounts.destination_owner.to_account_info(),
+            mint: accounts.mint.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+            token_program: token_program.clone(),
+        },
+    ))?;
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            token_program,
+            token_interface::TransferChecked {
+                from: accounts.token_account.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                to: accounts.destination_token.to_account_info(),
+                authority: accounts.owner.to_account_info(),
+            },
+        ),
+        1,
+        accounts.mint.decimals,
+    )
+}
+
+/// Point a Token-2022 strategy NFT's token-metadata URI at `uri`, topping up the
+/// mint's rent if the extension grows
+fn update_token_metadata_uri<'info>(
+    mint: &InterfaceAccount<'info, token_interface::Mint>,
+    token_program: &Program<'info, Token2022>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    collection_config: &Account<'info, CollectionConfig>,
+    uri: String,
+) -> Result<()> {
+    require!(uri.len() <= MAX_URI_LEN, MetaNftError::InvalidMetadataConfig);
+
+    let mint = mint.to_account_info();
+    let new_len = {
+        let data = mint.try_borrow_data()?;
+        let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+        let mut metadata = state.get_variable_len_extension::<TokenMetadata>()?;
+        let old_size = metadata.tlv_size_of()?;
+        metadata.uri = uri.clone();
+        data.len() - old_size + metadata.tlv_size_of()?
+    };
+    fund_rent(&mint, new_len, payer, system_program)?;
+
+    let seeds = &[b"collection".as_ref(), &[collection_config.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    token_metadata_update_field(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TokenMetadataUpdateField {
+                token_program_id: token_program.to_account_info(),
+                metadata: mint.clone(),
+                update_authority: collection_config.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        Field::Uri,
+        uri,
+    )
+}
+
+/// Point an existing strategy NFT's metadata at a new URI, keeping every other field
+fn update_metadata_uri<'info>(
+    metadata_program: &Program<'info, Metadata>,
+    metadata: &Account<'info, MetadataAccount>,
+    collection_config: &Account<'info, CollectionConfig>,
+    uri: String,
+) -> Result<()> {
+    require!(uri.len() <= MAX_URI_LEN, MetaNftError::InvalidMetadataConfig);
+
+    // Metaplex pads stored strings with NUL bytes
+    let data = DataV2 {
+        name: metadata.name.trim_end_matches('\0').to_string(),
+        symbol: metadata.symbol.trim_end_matches('\0').to_string(),
+        uri,
+        seller_fee_basis_points: metadata.seller_fee_basis_points,
+        creators: metadata.creators.clone(),
+        collection: metadata.collection.clone(),
+        uses: metadata.uses.clone(),
+    };
+
+    let seeds = &[b"collection".as_ref(), &[collection_config.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    update_metadata_accounts_v2(
+        CpiContext::new_with_signer(
+            metadata_program.to_account_info(),
+            UpdateMetadataAccountsV2 {
+                metadata: metadata.to_account_info(),
+                update_authority: collection_config.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        None,
+        Some(data),
+        None,
+        None,
+    )
+}
+
+/// Check that the previous instruction is the attestor's ed25519 signature over `attestation`
+fn verify_mint_attestation(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    attestation: &MintAttestation,
+) -> Result<()> {
+    let message = attestation_message(&crate::ID, "MintAttestation", &attestation.try_to_vec()?);
+    verify_ed25519_attestation(instructions_sysvar, signer, &message).map_err(|err| match err {
+        AttestationError::Missing => MetaNftError::MissingAttestation.into(),
+        AttestationError::Invalid => MetaNftError::InvalidAttestation.into(),
+    })
+}
+
+#[derive(Accounts)]
+pub struct InitializeCollection<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CollectionConfig::INIT_SPACE,
+        seeds = [b"collection"],
+        bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = 0,
+        mint::authority = collection_config,
+        mint::freeze_authority = collection_config,
+        seeds = [b"collection_mint"],
+        bump
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = collection_mint,
+        associated_token::authority = collection_config,
+    )]
+    pub collection_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Collection metadata account (created via CPI)
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Collection master edition account (created via CPI)
+    #[account(mut)]
+    pub collection_master_edition: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateCollectionConfig<'info> {
+    /// CHECK: Legacy-layout `CollectionConfig`; owner, discriminator, size and authority
+    /// are checked in the handler
+    #[account(
+        mut,
+        seeds = [b"collection"],
+        bump
+    )]
+    pub collection_config: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = 0,
+        mint::authority = collection_config,
+        mint::freeze_authority = collection_config,
+        seeds = [b"collection_mint"],
+        bump
+    )]
+    pub collection_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = collection_mint,
+        associated_token::authority = collection_config,
+    )]
+    pub collection_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Collection metadata account (created via CPI)
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Collection master edition account (created via CPI)
+    #[account(mut)]
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(strategy_id: String, genes_hash: [u8; 32])]
+pub struct MintStrategyNft<'info> {
+    #[account(
+        mut,
+        seeds = [b"collection"],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StrategyNftData::INIT_SPACE,
+        seeds = [b"strategy_nft", strategy_id.as_bytes()],
+        bump
+    )]
+    pub strategy_nft: Account<'info, StrategyNftData>,
+
+    /// Fails to initialize if these genes were already minted
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + GenesRegistry::INIT_SPACE,
+        seeds = [b"genes_registry", genes_hash.as_ref()],
+        bump
+    )]
+    pub genes_registry: Account<'info, GenesRegistry>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PerformanceHistory::INIT_SPACE,
+        seeds = [b"history", mint.key().as_ref()],
+        bump
+    )]
+    pub performance_history: Box<Account<'info, PerformanceHistory>>,
+
+    /// Optional published genes, checked against `genes_hash`
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StrategyGenesRecord::INIT_SPACE,
+        seeds = [b"genes", mint.key().as_ref()],
+        bump
+    )]
+    pub strategy_genes: Option<Box<Account<'info, StrategyGenesRecord>>>,
+
+    /// CHECK: Admin-configured `EditionSupply`, read only if it has been created
+    #[account(
+        seeds = [b"edition_supply", strategy_id.as_bytes()],
+        bump
+    )]
+    pub edition_supply: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"strategy_nft", parent_a_nft.strategy_id.as_bytes()],
+        bump = parent_a_nft.bump
+    )]
+    pub parent_a_nft: Option<Box<Account<'info, StrategyNftData>>>,
+
+    #[account(
+        seeds = [b"strategy_nft", parent_b_nft.strategy_id.as_bytes()],
+        bump = parent_b_nft.bump
+    )]
+    pub parent_b_nft: Option<Box<Account<'info, StrategyNftData>>>,
+
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = collection_config,
+        mint::freeze_authority = collection_config,
+    )]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Metadata account (created via CPI)
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Master edition account (created via CPI)
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+
+    #[account(address = collection_config.collection_mint @ MetaNftError::InvalidCollection)]
+    pub collection_mint: Account<'info, Mint>,
+
+    /// CHECK: Collection metadata account, validated by the metadata program
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Collection master edition account, validated by the metadata program
+    pub collection_master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: pNFT token record (created via CPI), unused for standard NFTs
+    #[account(mut)]
+    pub token_record: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Rule set the collection enforces on pNFTs, checked against `rule_set`
+    pub authorization_rules: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token auth rules program, validated by the metadata program
+    pub authorization_rules_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: meta_treasury state PDA, receives mint fees through `deposit_revenue`
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+        seeds::program = meta_treasury::ID
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub treasury_program: Program<'info, MetaTreasury>,
+
+    /// Pays rent and the mint fee
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Wallet that receives the NFT; bound by the attestation's `owner`
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, used to read the preceding ed25519 instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(strategy_id: String, genes_hash: [u8; 32])]
+pub struct MintStrategyNft2022<'info> {
+    #[account(
+        mut,
+        seeds = [b"collection"],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StrategyNftData::INIT_SPACE,
+        seeds = [b"strategy_nft", strategy_id.as_bytes()],
+        bump
+    )]
+    pub strategy_nft: Account<'info, StrategyNftData>,
+
+    /// Fails to initialize if these genes were already minted
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + GenesRegistry::INIT_SPACE,
+        seeds = [b"genes_registry", genes_hash.as_ref()],
+        bump
+    )]
+    pub genes_registry: Account<'info, GenesRegistry>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PerformanceHistory::INIT_SPACE,
+        seeds = [b"history", mint.key().as_ref()],
+        bump
+    )]
+    pub performance_history: Box<Account<'info, PerformanceHistory>>,
+
+    /// Optional published genes, checked against `genes_hash`
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + StrategyGenesRecord::INIT_SPACE,
+        seeds = [b"genes", mint.key().as_ref()],
+        bump
+    )]
+    pub strategy_genes: Option<Box<Account<'info, StrategyGenesRecord>>>,
+
+    #[account(
+        seeds = [b"strategy_nft", parent_a_nft.strategy_id.as_bytes()],
+        bump = parent_a_nft.bump
+    )]
+    pub parent_a_nft: Option<Box<Account<'info, StrategyNftData>>>,
+
+    #[account(
+        seeds = [b"strategy_nft", parent_b_nft.strategy_id.as_bytes()],
+        bump = parent_b_nft.bump
+    )]
+    pub parent_b_nft: Option<Box<Account<'info, StrategyNftData>>>,
+
+    /// Token-2022 mint whose metadata pointer points at itself
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = collection_config,
+        mint::token_program = token_program,
+        extensions::metadata_pointer::authority = collection_config,
+        extensions::metadata_pointer::metadata_address = mint,
+    )]
+    pub mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: meta_treasury state PDA, receives mint fees through `deposit_revenue`
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+        seeds::program = meta_treasury::ID
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub treasury_program: Program<'info, MetaTreasury>,
+
+    /// Pays rent and the mint fee
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Wallet that receives the NFT; bound by the attestation's `owner`
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, used to read the preceding ed25519 instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SyncTokenMetadata<'info> {
+    #[account(
+        seeds = [b"collection"],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        seeds = [b"strategy_nft", strategy_nft.strategy_id.as_bytes()],
+        bump = strategy_nft.bump
+    )]
+    pub strategy_nft: Account<'info, StrategyNftData>,
+
+    #[account(mut, address = strategy_nft.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    /// Covers rent if the fields grow
+    #[account(mut)]
     pub payer: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub metadata_program: Program<'info, Metadata>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateStrategyStats<'info> {
+    #[account(
+        seeds = [b"collection"],
+        bump = collection_config.bump,
+        constraint = attestor.key() == collection_config.attestor @ MetaNftError::Unauthorized
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"strategy_nft", strategy_nft.strategy_id.as_bytes()],
+        bump = strategy_nft.bump
+    )]
+    pub strategy_nft: Account<'info, StrategyNftData>,
+
+    #[account(
+        mut,
+        seeds = [b"history", strategy_nft.mint.as_ref()],
+        bump = performance_history.bump
+    )]
+    pub performance_history: Box<Account<'info, PerformanceHistory>>,
+
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), strategy_nft.mint.as_ref()],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    /// Metaplex metadata, only needed to change the URI; Token-2022 NFTs have none
+    pub metadata: Option<Account<'info, MetadataAccount>>,
+
+    /// Mint holding a Token-2022 NFT's metadata, only needed to change its URI
+    #[account(mut, address = strategy_nft.mint)]
+    pub mint: Option<Box<InterfaceAccount<'info, token_interface::Mint>>>,
+
+    /// Covers rent if a Token-2022 NFT's metadata grows
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+
+    pub metadata_program: Program<'info, Metadata>,
+    pub token_program: Option<Program<'info, Token2022>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateNftRoyalties<'info> {
+    #[account(
+        seeds = [b"collection"],
+        bump = collection_config.bump,
+        constraint = attestor.key() == collection_config.attestor @ MetaNftError::Unauthorized
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"strategy_nft", strategy_nft.strategy_id.as_bytes()],
+        bump = strategy_nft.bump
+    )]
+    pub strategy_nft: Account<'info, StrategyNftData>,
+
+    /// CHECK: Metaplex metadata PDA, deserialized in the handler once the NFT is known
+    /// to have one
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), strategy_nft.mint.as_ref()],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    pub attestor: Signer<'info>,
+
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+#[derive(Accounts)]
+pub struct RecordStrategyDeath<'info> {
+    #[account(
+        seeds = [b"collection"],
+        bump = collection_config.bump,
+        constraint = attestor.key() == collection_config.attestor @ MetaNftError::Unauthorized
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"strategy_nft", strategy_nft.strategy_id.as_bytes()],
+        bump = strategy_nft.bump
+    )]
+    pub strategy_nft: Account<'info, StrategyNftData>,
+
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), strategy_nft.mint.as_ref()],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    /// Metaplex metadata, only needed to change the URI; Token-2022 NFTs have none
+    pub metadata: Option<Account<'info, MetadataAccount>>,
+
+    /// Mint holding a Token-2022 NFT's metadata, only needed to change its URI
+    #[account(mut, address = strategy_nft.mint)]
+    pub mint: Option<Box<InterfaceAccount<'info, token_interface::Mint>>>,
+
+    /// Covers rent if a Token-2022 NFT's metadata grows
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+
+    pub metadata_program: Program<'info, Metadata>,
+    pub token_program: Option<Program<'info, Token2022>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MintEdition<'info> {
+    #[account(
+        seeds = [b"collection"],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Box<Account<'info, CollectionConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"strategy_nft", strategy_nft.strategy_id.as_bytes()],
+        bump = strategy_nft.bump
+    )]
+    pub strategy_nft: Box<Account<'info, StrategyNftData>>,
+
+    #[account(address = strategy_nft.mint)]
+    pub master_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Master metadata account, validated by the metadata program
+    pub master_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Master edition account, validated and updated by the metadata program
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+
+    /// Holds the master NFT and signs prints in place of its holder
+    #[account(
+        seeds = [b"edition_escrow", strategy_nft.mint.as_ref()],
+        bump = edition_escrow.bump
+    )]
+    pub edition_escrow: Box<Account<'info, EditionEscrow>>,
+
+    #[account(
+        associated_token::mint = master_mint,
+        associated_token::authority = edition_escrow,
+        constraint = master_token_account.amount == 1 @ MetaNftError::NotNftHolder
+    )]
+    pub master_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = buyer,
+        mint::decimals = 0,
+        mint::authority = collection_config,
+        mint::freeze_authority = collection_config,
+    )]
+    pub edition_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = buyer,
+        associated_token::mint = edition_mint,
+        associated_token::authority = buyer,
+    )]
+    pub edition_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Edition metadata account (created via CPI)
+    #[account(mut)]
+    pub edition_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Edition account (created via CPI)
+    #[account(mut)]
+    pub edition: UncheckedAccount<'info>,
+
+    /// CHECK: Edition marker PDA, validated by the metadata program
+    #[account(mut)]
+    pub edition_mark: UncheckedAccount<'info>,
+
+    /// CHECK: meta_treasury state PDA, receives the edition price through `deposit_revenue`
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+        seeds::program = meta_treasury::ID
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub treasury_program: Program<'info, MetaTreasury>,
+
+    /// Pays the edition price and rent, and receives the edition
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct EscrowMasterEdition<'info> {
+    #[account(
+        seeds = [b"strategy_nft", strategy_nft.strategy_id.as_bytes()],
+        bump = strategy_nft.bump
+    )]
+    pub strategy_nft: Box<Account<'info, StrategyNftData>>,
+
+    #[account(
+        init,
+        payer = holder,
+        space = 8 + EditionEscrow::INIT_SPACE,
+        seeds = [b"edition_escrow", strategy_nft.mint.as_ref()],
+        bump
+    )]
+    pub edition_escrow: Box<Account<'info, EditionEscrow>>,
+
+    #[account(address = strategy_nft.mint)]
+    pub master_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = holder_token_account.mint == strategy_nft.mint @ MetaNftError::NotNftHolder,
+        constraint = holder_token_account.owner == holder.key() @ MetaNftError::NotNftHolder,
+        constraint = holder_token_account.amount == 1 @ MetaNftError::NotNftHolder
+    )]
+    pub holder_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = holder,
+        associated_token::mint = master_mint,
+        associated_token::authority = edition_escrow,
+    )]
+    pub escrow_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseMasterEdition<'info> {
+    #[account(
+        seeds = [b"strategy_nft", strategy_nft.strategy_id.as_bytes()],
+        bump = strategy_nft.bump
+    )]
+    pub strategy_nft: Box<Account<'info, StrategyNftData>>,
+
+    #[account(
+        mut,
+        close = depositor,
+        seeds = [b"edition_escrow", strategy_nft.mint.as_ref()],
+        bump = edition_escrow.bump,
+        has_one = depositor @ MetaNftError::Unauthorized
+    )]
+    pub edition_escrow: Box<Account<'info, EditionEscrow>>,
+
+    #[account(
+        mut,
+        associated_token::mint = edition_escrow.mint,
+        associated_token::authority = edition_escrow,
+    )]
+    pub escrow_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = depositor_token_account.mint == strategy_nft.mint @ MetaNftError::NotNftHolder,
+        constraint = depositor_token_account.owner == depositor.key() @ MetaNftError::NotNftHolder
+    )]
+    pub depositor_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(strategy_id: String)]
+pub struct SetEditionSupply<'info> {
+    #[account(
+        seeds = [b"collection"],
+        bump = collection_config.bump,
+        constraint = authority.key() == collection_config.authority @ MetaNftError::Unauthorized
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + EditionSupply::INIT_SPACE,
+        seeds = [b"edition_supply", strategy_id.as_bytes()],
+        bump
+    )]
+    pub edition_supply: Account<'info, EditionSupply>,
+
+    /// CHECK: Must still be empty, the strategy cannot have been minted yet
+    #[account(
+        seeds = [b"strategy_nft", strategy_id.as_bytes()],
+        bump
+    )]
+    pub strategy_nft: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestBreeding<'info> {
+    #[account(
+        mut,
+        seeds = [b"collection"],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        init,
+        payer = owner_a,
+        space = 8 + BreedingRequest::INIT_SPACE,
+        seeds = [b"breeding", collection_config.total_breeding_requests.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub breeding_request: Account<'info, BreedingRequest>,
+
+    /// Holds the breeding fee until the request is fulfilled or refunded
+    #[account(
+        mut,
+        seeds = [b"breeding_escrow", breeding_request.key().as_ref()],
+        bump
+    )]
+    pub escrow: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"strategy_nft", parent_a_nft.strategy_id.as_bytes()],
+        bump = parent_a_nft.bump
+    )]
+    pub parent_a_nft: Box<Account<'info, StrategyNftData>>,
+
+    #[account(
+        mut,
+        seeds = [b"strategy_nft", parent_b_nft.strategy_id.as_bytes()],
+        bump = parent_b_nft.bump
+    )]
+    pub parent_b_nft: Box<Account<'info, StrategyNftData>>,
+
+    #[account(
+        constraint = parent_a_token.mint == parent_a_nft.mint @ MetaNftError::NotNftHolder,
+        constraint = parent_a_token.owner == owner_a.key() @ MetaNftError::NotNftHolder,
+        constraint = parent_a_token.amount == 1 @ MetaNftError::NotNftHolder
+    )]
+    pub parent_a_token: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    #[account(
+        constraint = parent_b_token.mint == parent_b_nft.mint @ MetaNftError::NotNftHolder,
+        constraint = parent_b_token.owner == owner_b.key() @ MetaNftError::NotNftHolder,
+        constraint = parent_b_token.amount == 1 @ MetaNftError::NotNftHolder
+    )]
+    pub parent_b_token: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Holder of parent A; pays the fee and receives the child
+    #[account(mut)]
+    pub owner_a: Signer<'info>,
+
+    /// Holder of parent B, may be the same wallet as `owner_a`
+    pub owner_b: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FulfilBreeding<'info> {
+    #[account(
+        seeds = [b"collection"],
+        bump = collection_config.bump,
+        constraint = attestor.key() == collection_config.attestor @ MetaNftError::Unauthorized
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"breeding", breeding_request.id.to_le_bytes().as_ref()],
+        bump = breeding_request.bump
+    )]
+    pub breeding_request: Account<'info, BreedingRequest>,
+
+    #[account(
+        mut,
+        seeds = [b"breeding_escrow", breeding_request.key().as_ref()],
+        bump = breeding_request.escrow_bump
+    )]
+    pub escrow: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"strategy_nft", child_nft.strategy_id.as_bytes()],
+        bump = child_nft.bump
+    )]
+    pub child_nft: Box<Account<'info, StrategyNftData>>,
+
+    /// Token account holding the child, which must belong to the requester
+    #[account(
+        constraint = child_token.mint == child_nft.mint @ MetaNftError::InvalidChild,
+        constraint = child_token.owner == breeding_request.requester @ MetaNftError::InvalidChild,
+        constraint = child_token.amount == 1 @ MetaNftError::InvalidChild
+    )]
+    pub child_token: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// Created here, so a child that already fulfilled a request is rejected
+    #[account(
+        init,
+        payer = attestor,
+        space = 8 + BredChild::INIT_SPACE,
+        seeds = [b"bred_child", child_nft.mint.as_ref()],
+        bump
+    )]
+    pub bred_child: Account<'info, BredChild>,
+
+    #[account(
+        mut,
+        constraint = parent_a_nft.mint == breeding_request.parent_a @ MetaNftError::InvalidParent
+    )]
+    pub parent_a_nft: Box<Account<'info, StrategyNftData>>,
+
+    #[account(
+        mut,
+        constraint = parent_b_nft.mint == breeding_request.parent_b @ MetaNftError::InvalidParent
+    )]
+    pub parent_b_nft: Box<Account<'info, StrategyNftData>>,
+
+    /// CHECK: meta_treasury state PDA, receives the breeding fee through `deposit_revenue`
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+        seeds::program = meta_treasury::ID
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    pub treasury_program: Program<'info, MetaTreasury>,
+
+    /// Pays for the `bred_child` record
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefundBreeding<'info> {
+    #[account(
+        seeds = [b"collection"],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"breeding", breeding_request.id.to_le_bytes().as_ref()],
+        bump = breeding_request.bump
+    )]
+    pub breeding_request: Account<'info, BreedingRequest>,
+
+    #[account(
+        mut,
+        seeds = [b"breeding_escrow", breeding_request.key().as_ref()],
+        bump = breeding_request.escrow_bump
+    )]
+    pub escrow: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = parent_a_nft.mint == breeding_request.parent_a @ MetaNftError::InvalidParent
+    )]
+    pub parent_a_nft: Box<Account<'info, StrategyNftData>>,
+
+    #[account(
+        mut,
+        constraint = parent_b_nft.mint == breeding_request.parent_b @ MetaNftError::InvalidParent
+    )]
+    pub parent_b_nft: Box<Account<'info, StrategyNftData>>,
+
+    #[account(mut, address = breeding_request.requester)]
+    pub requester: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BurnStrategyNft<'info> {
+    #[account(
+        mut,
+        seeds = [b"collection"],
+        bump = collection_config.bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"strategy_nft", strategy_nft.strategy_id.as_bytes()],
+        bump = strategy_nft.bump
+    )]
+    pub strategy_nft: Account<'info, StrategyNftData>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"history", strategy_nft.mint.as_ref()],
+        bump = performance_history.bump
+    )]
+    pub performance_history: Box<Account<'info, PerformanceHistory>>,
+
+    /// Published genes, when the strategy was minted with them
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"genes", strategy_nft.mint.as_ref()],
+        bump = strategy_genes.bump
+    )]
+    pub strategy_genes: Option<Box<Account<'info, StrategyGenesRecord>>>,
+
+    #[account(mut, address = strategy_nft.mint)]
+    pub mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = token_account.mint == strategy_nft.mint @ MetaNftError::NotNftHolder,
+        constraint = token_account.owner == owner.key() @ MetaNftError::NotNftHolder,
+        constraint = token_account.amount == 1 @ MetaNftError::NotNftHolder
+    )]
+    pub token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: Metadata account, validated and closed by the metadata program; unused for Token-2022 NFTs
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Master edition account, validated and closed by the metadata program
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Collection metadata account, validated by the metadata program
+    #[account(mut)]
+    pub collection_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: pNFT token record, validated and closed by the metadata program
+    #[account(mut)]
+    pub token_record: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, required by the metadata program's `BurnV1`
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// SPL Token for Metaplex NFTs, Token-2022 for Token-2022 NFTs
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferStrategyNft<'info> {
+    #[account(
+        mut,
+        seeds = [b"strategy_nft", strategy_nft.strategy_id.as_bytes()],
+        bump = strategy_nft.bump
+    )]
+    pub strategy_nft: Account<'info, StrategyNftData>,
+
+    #[account(address = strategy_nft.mint)]
+    pub mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(
+        mut,
+        constraint = token_account.mint == strategy_nft.mint @ MetaNftError::NotNftHolder,
+        constraint = token_account.owner == owner.key() @ MetaNftError::NotNftHolder,
+        constraint = token_account.amount == 1 @ MetaNftError::NotNftHolder
+    )]
+    pub token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: Wallet receiving the NFT
+    pub destination_owner: UncheckedAccount<'info>,
+
+    /// CHECK: Receiver's associated token account, created if needed by the metadata or
+    /// associated token program
+    #[account(mut)]
+    pub destination_token: UncheckedAccount<'info>,
+
+    /// CHECK: Metadata account, validated by the metadata program; unused for Token-2022 NFTs
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Master edition account, validated by the metadata program
+    pub master_edition: UncheckedAccount<'info>,
+
+    /// CHECK: Holder's pNFT token record, unused for standard NFTs
+    #[account(mut)]
+    pub token_record: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Receiver's pNFT token record (created via CPI), unused for standard NFTs
+    #[account(mut)]
+    pub destination_token_record: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Rule set on the pNFT's metadata, validated by the metadata program
+    pub authorization_rules: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token auth rules program, validated by the metadata program
+    pub authorization_rules_program: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, required by the metadata program's `TransferV1`
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// SPL Token for Metaplex NFTs, Token-2022 for Token-2022 NFTs
+    pub token_program: Interface<'info, token_interface::TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SyncOwner<'info> {
+    #[account(
+        mut,
+        seeds = [b"strategy_nft", strategy_nft.strategy_id.as_bytes()],
+        bump = strategy_nft.bump
+    )]
+    pub strategy_nft: Account<'info, StrategyNftData>,
+
+    /// Holder's token account; owner-gated instructions check this instead of the cached `owner`
+    #[account(
+        constraint = token_account.mint == strategy_nft.mint @ MetaNftError::NotNftHolder,
+        constraint = token_account.amount == 1 @ MetaNftError::NotNftHolder
+    )]
+    pub token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(strategy_id: String, genes_hash: [u8; 32])]
+pub struct MigrateStrategyNft<'info> {
+    #[account(
+        seeds = [b"collection"],
+        bump = collection_config.bump,
+        constraint = authority.key() == collection_config.authority @ MetaNftError::Unauthorized
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    /// CHECK: Legacy-layout `StrategyNftData`; owner, discriminator and size are checked in the handler
+    #[account(
+        mut,
+        seeds = [b"strategy_nft", strategy_id.as_bytes()],
+        bump
+    )]
+    pub strategy_nft: UncheckedAccount<'info>,
+
+    /// CHECK: Must match the mint stored in the legacy account
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PerformanceHistory::INIT_SPACE,
+        seeds = [b"history", mint.key().as_ref()],
+        bump
+    )]
+    pub performance_history: Box<Account<'info, PerformanceHistory>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GenesRegistry::INIT_SPACE,
+        seeds = [b"genes_registry", genes_hash.as_ref()],
+        bump
+    )]
+    pub genes_registry: Account<'info, GenesRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
@@ -325,11 +3323,342 @@ pub struct CollectionConfig {
     pub mint_price_lamports: u64,
     pub is_active: bool,
     pub bump: u8,
+    /// Verified collection NFT every strategy NFT is minted into
+    pub collection_mint: Pubkey,
+    /// New NFTs are named "<name_prefix> #<mint number>"
+    #[max_len(MAX_NAME_PREFIX_LEN)]
+    pub name_prefix: String,
+    #[max_len(MAX_SYMBOL_LEN)]
+    pub symbol: String,
+    /// New NFTs point at "<base_uri><strategy_id>.json"
+    #[max_len(MAX_BASE_URI_LEN)]
+    pub base_uri: String,
+    pub seller_fee_basis_points: u16,
+    #[max_len(MAX_CREATORS)]
+    pub creators: Vec<CreatorShare>,
+    /// Engine key whose ed25519 signature every mint requires
+    pub attestor: Pubkey,
+    pub total_burned: u64,
+    pub breeding_fee_lamports: u64,
+    pub breeding_timeout_seconds: i64,
+    /// Also the id of the next breeding request
+    pub total_breeding_requests: u64,
+    pub edition_price_lamports: u64,
+    /// Token standard new strategy NFTs are minted as
+    pub token_standard: NftStandard,
+    /// Metaplex rule set attached to new pNFTs
+    pub rule_set: Option<Pubkey>,
+}
+
+/// Metaplex token standard of a strategy NFT
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum NftStandard {
+    NonFungible,
+    /// Frozen token account that only moves through Metaplex, enforcing royalties
+    ProgrammableNonFungible,
+    /// Token-2022 mint holding its own metadata, minted with `mint_strategy_nft_2022`
+    Token2022,
+}
+
+impl CollectionConfig {
+    /// Metaplex creators for new mints; only the config PDA can sign as a verified creator
+    pub fn metadata_creators(&self, config_key: Pubkey) -> Vec<mpl_token_metadata::types::Creator> {
+        to_metadata_creators(&self.creators, config_key)
+    }
+
+    /// Royalties and Metaplex creators for a strategy, its override winning over the
+    /// collection defaults
+    pub fn royalties(
+        &self,
+        config_key: Pubkey,
+        royalty_override: &Option<RoyaltyOverride>,
+    ) -> (u16, Vec<mpl_token_metadata::types::Creator>) {
+        match royalty_override {
+            Some(royalty) => (
+                royalty.seller_fee_basis_points,
+                to_metadata_creators(&royalty.creators, config_key),
+            ),
+            None => (self.seller_fee_basis_points, self.metadata_creators(config_key)),
+        }
+    }
+}
+
+fn to_metadata_creators(
+    creators: &[CreatorShare],
+    config_key: Pubkey,
+) -> Vec<mpl_token_metadata::types::Creator> {
+    creators
+        .iter()
+        .map(|c| mpl_token_metadata::types::Creator {
+            address: c.address,
+            verified: c.address == config_key,
+            share: c.share,
+        })
+        .collect()
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct CreatorShare {
+    pub address: Pubkey,
+    /// Percentage of royalties, all shares must add up to 100
+    pub share: u8,
+}
+
+/// Per-strategy royalties signed off by the attestor, e.g. to pay a strategy's discoverer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct RoyaltyOverride {
+    pub seller_fee_basis_points: u16,
+    #[max_len(MAX_CREATORS)]
+    pub creators: Vec<CreatorShare>,
+}
+
+/// Strategy data signed by the engine to authorize a mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MintAttestation {
+    pub strategy_id: String,
+    pub genes_hash: [u8; 32],
+    pub archetype: String,
+    pub generation: u32,
+    pub fitness_score: u64,
+    pub total_pnl: i64,
+    pub win_rate: u64,
+    pub trades_executed: u32,
+    pub sharpe_ratio_milli: i32,
+    pub max_drawdown_bps: u16,
+    pub avg_hold_time_secs: u32,
+    pub parent_a: Option<Pubkey>,
+    pub parent_b: Option<Pubkey>,
+    pub royalty_override: Option<RoyaltyOverride>,
+    /// Wallet the NFT is minted to
+    pub owner: Pubkey,
+    /// Unix timestamp after which the attestation can no longer be used
+    pub expires_at: i64,
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct StrategyNftData {
+    pub mint: Pubkey,
+    #[max_len(64)]
+    pub strategy_id: String,
+    /// sha256 of the Borsh-encoded `StrategyGenes`
+    pub genes_hash: [u8; 32],
+    /// Holder as of mint or the last `sync_owner`; may be stale after a transfer
+    pub owner: Pubkey,
+    pub minted_at: i64,
+    pub mint_price: u64,
+    #[max_len(32)]
+    pub archetype: String,
+    pub generation: u32,
+    /// Fitness in hundredths (8500 = 85.00)
+    pub fitness_score: u64,
+    /// Realized PnL in lamports
+    pub total_pnl: i64,
+    /// Share of winning trades in basis points (6800 = 68.00%)
+    pub win_rate: u64,
+    pub trades_executed: u32,
+    /// Sharpe ratio in milli-units (1250 = 1.25), within +/-`MAX_SHARPE_RATIO_MILLI`
+    pub sharpe_ratio_milli: i32,
+    /// Largest peak-to-trough loss in basis points
+    pub max_drawdown_bps: u16,
+    /// Average position hold time in seconds
+    pub avg_hold_time_secs: u32,
+    pub bump: u8,
+    /// When the stats above were last refreshed
+    pub last_updated: i64,
+    pub status: StrategyStatus,
+    /// When evolution killed the strategy, 0 while it is alive
+    pub died_at: i64,
+    /// Mints of the parent strategies, `None` for genesis strategies
+    pub parent_a: Option<Pubkey>,
+    pub parent_b: Option<Pubkey>,
+    /// Editions the master can print, fixed at mint
+    pub max_editions: u64,
+    pub editions_printed: u64,
+    /// Royalties replacing the collection defaults, `None` to follow the collection
+    pub royalty_override: Option<RoyaltyOverride>,
+    pub token_standard: NftStandard,
+}
+
+/// Lifecycle of the strategy behind an NFT, mirroring the engine's `StrategyStatus`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum StrategyStatus {
+    Active,
+    Breeding,
+    Dead,
+}
+
+impl StrategyNftData {
+    /// Fill a freshly created account from the attestation it was minted with
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_mint(
+        &mut self,
+        attestation: &MintAttestation,
+        mint: Pubkey,
+        minted_at: i64,
+        mint_price: u64,
+        max_editions: u64,
+        token_standard: NftStandard,
+        bump: u8,
+    ) {
+        self.mint = mint;
+        self.strategy_id = attestation.strategy_id.clone();
+        self.genes_hash = attestation.genes_hash;
+        self.owner = attestation.owner;
+        self.minted_at = minted_at;
+        self.mint_price = mint_price;
+        self.archetype = attestation.archetype.clone();
+        self.generation = attestation.generation;
+        self.fitness_score = attestation.fitness_score;
+        self.total_pnl = attestation.total_pnl;
+        self.win_rate = attestation.win_rate;
+        self.trades_executed = attestation.trades_executed;
+        self.sharpe_ratio_milli = attestation.sharpe_ratio_milli;
+        self.max_drawdown_bps = attestation.max_drawdown_bps;
+        self.avg_hold_time_secs = attestation.avg_hold_time_secs;
+        self.last_updated = minted_at;
+        self.status = StrategyStatus::Active;
+        self.died_at = 0;
+        self.parent_a = attestation.parent_a;
+        self.parent_b = attestation.parent_b;
+        self.max_editions = max_editions;
+        self.editions_printed = 0;
+        self.royalty_override = attestation.royalty_override.clone();
+        self.token_standard = token_standard;
+        self.bump = bump;
+    }
+
+    pub fn snapshot(&self) -> PerformanceSnapshot {
+        PerformanceSnapshot {
+            timestamp: self.last_updated,
+            generation: self.generation,
+            fitness_score: self.fitness_score,
+            total_pnl: self.total_pnl,
+            win_rate: self.win_rate,
+            trades_executed: self.trades_executed,
+        }
+    }
+}
+
+/// Compact encoding of the engine's `StrategyGenes` (packages/core/src/types.ts)
+///
+/// Fractions are stored in basis points, USD amounts as whole dollars and pattern or
+/// keyword lists as bitmasks over `BUY_PATTERNS`, `TOKEN_KEYWORDS` and `SELL_PATTERNS`,
+/// where bit `i` stands for the list's `i`-th entry. Whale wallets are not encoded.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct StrategyGenes {
+    pub entry_mcap_min: u64,
+    pub entry_mcap_max: u64,
+    pub entry_volume_min: u64,
+    pub twitter_followers_min: u32,
+    pub telegram_members_min: u32,
+    pub holders_min: u32,
+    pub buy_patterns: u16,
+    pub token_keywords: u32,
+    /// Exit price as a multiple of entry, in basis points (25000 = 2.5x)
+    pub take_profit_bps: u32,
+    pub stop_loss_bps: u32,
+    /// Maximum hold time in minutes
+    pub time_based_exit_mins: u16,
+    pub volume_drop_exit_bps: u16,
+    /// Bit 0: momentum reversal, bit 1: volume dry, bit 2: holders dumping
+    pub sell_signal_flags: u8,
+    /// 0 disables the ceiling
+    pub mcap_ceiling: u64,
+    pub profit_securing_bps: u16,
+    pub trailing_stop_bps: u16,
+    pub sell_patterns: u16,
+    pub investment_bps: u16,
+    pub max_simultaneous_positions: u8,
+    pub max_drawdown_bps: u16,
+    pub diversification_bps: u16,
+}
+
+/// Edition supply for a strategy, set by the admin before it is minted
+#[account]
+#[derive(InitSpace)]
+pub struct EditionSupply {
+    pub max_supply: u64,
+    pub bump: u8,
+}
+
+/// Custody of a master NFT whose editions anyone can print
+#[account]
+#[derive(InitSpace)]
+pub struct EditionEscrow {
+    pub mint: Pubkey,
+    /// Holder who escrowed the master and can take it back
+    pub depositor: Pubkey,
+    pub bump: u8,
+}
+
+/// A holder's request for the engine to breed two strategy NFTs
+#[account]
+#[derive(InitSpace)]
+pub struct BreedingRequest {
+    pub id: u64,
+    /// Holder of parent A, who paid the fee and receives the child
+    pub requester: Pubkey,
+    pub parent_a: Pubkey,
+    pub parent_b: Pubkey,
+    /// Lamports held in the request's escrow PDA
+    pub fee: u64,
+    pub created_at: i64,
+    pub status: BreedingStatus,
+    /// Set once fulfilled
+    pub child_mint: Pubkey,
+    pub bump: u8,
+    pub escrow_bump: u8,
+}
+
+/// Marks a child strategy as used to fulfil a breeding request
+#[account]
+#[derive(InitSpace)]
+pub struct BredChild {
+    pub request_id: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum BreedingStatus {
+    Pending,
+    Fulfilled,
+    Refunded,
+}
+
+/// Marks a genes hash as minted, so every strategy NFT carries distinct genes
+#[account]
+#[derive(InitSpace)]
+pub struct GenesRegistry {
+    pub genes_hash: [u8; 32],
+    pub mint: Pubkey,
+    pub bump: u8,
+}
+
+/// Genes published alongside a strategy NFT
+#[account]
+#[derive(InitSpace)]
+pub struct StrategyGenesRecord {
+    pub mint: Pubkey,
+    pub genes: StrategyGenes,
+    pub bump: u8,
+}
+
+/// `CollectionConfig` as laid out before the collection NFT, branding, royalties and
+/// attestor were stored on it, read by `migrate_collection_config`
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyCollectionConfig {
+    pub authority: Pubkey,
+    pub total_minted: u64,
+    pub mint_price_lamports: u64,
+    pub is_active: bool,
+    pub bump: u8,
+}
+
+/// `StrategyNftData` as laid out before the extended performance fields, read by
+/// `migrate_strategy_nft`
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyStrategyNftData {
     pub mint: Pubkey,
     #[max_len(64)]
     pub strategy_id: String,
@@ -348,6 +3677,44 @@ pub struct StrategyNftData {
     pub bump: u8,
 }
 
+/// Ring buffer of a strategy's stats, appended on mint and on every stats update
+#[account]
+#[derive(InitSpace)]
+pub struct PerformanceHistory {
+    pub mint: Pubkey,
+    /// Slot the next snapshot is written to
+    pub head: u8,
+    /// Number of valid snapshots, at most `HISTORY_CAPACITY`
+    pub len: u8,
+    pub snapshots: [PerformanceSnapshot; HISTORY_CAPACITY],
+    pub bump: u8,
+}
+
+impl PerformanceHistory {
+    pub fn push(&mut self, snapshot: PerformanceSnapshot) {
+        self.snapshots[self.head as usize] = snapshot;
+        self.head = ((self.head as usize + 1) % HISTORY_CAPACITY) as u8;
+        self.len = (self.len as usize + 1).min(HISTORY_CAPACITY) as u8;
+    }
+
+    /// Snapshots from oldest to newest, for off-chain readers of the account
+    pub fn snapshots(&self) -> impl Iterator<Item = &PerformanceSnapshot> + '_ {
+        let len = self.len as usize;
+        let start = (self.head as usize + HISTORY_CAPACITY - len) % HISTORY_CAPACITY;
+        (0..len).map(move |i| &self.snapshots[(start + i) % HISTORY_CAPACITY])
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct PerformanceSnapshot {
+    pub timestamp: i64,
+    pub generation: u32,
+    pub fitness_score: u64,
+    pub total_pnl: i64,
+    pub win_rate: u64,
+    pub trades_executed: u32,
+}
+
 #[error_code]
 pub enum MetaNftError {
     #[msg("Minting is currently paused")]
@@ -356,30 +3723,243 @@ pub enum MetaNftError {
     Unauthorized,
     #[msg("Invalid strategy data")]
     InvalidStrategyData,
+    #[msg("Collection mint does not match the config")]
+    InvalidCollection,
+    #[msg("Invalid name prefix, symbol or base URI")]
+    InvalidMetadataConfig,
+    #[msg("Invalid royalty or creator configuration")]
+    InvalidRoyaltyConfig,
+    #[msg("Attestor not set")]
+    AttestorNotSet,
+    #[msg("Attestation has expired")]
+    AttestationExpired,
+    #[msg("Missing ed25519 attestation instruction")]
+    MissingAttestation,
+    #[msg("Attestation does not match the expected signer or message")]
+    InvalidAttestation,
+    #[msg("Strategy NFT account already uses the current layout")]
+    AlreadyMigrated,
+    #[msg("Genes do not hash to the strategy's genes hash")]
+    GenesHashMismatch,
+    #[msg("Token account does not hold this strategy NFT")]
+    NotNftHolder,
+    #[msg("Strategy is dead")]
+    StrategyDead,
+    #[msg("Parent mint does not match the parent strategy account")]
+    InvalidParent,
+    #[msg("Parent strategy is not active")]
+    ParentNotActive,
+    #[msg("Strategy has an open breeding request")]
+    StrategyBreeding,
+    #[msg("Breeding request is not pending")]
+    BreedingNotPending,
+    #[msg("Breeding request has not timed out yet")]
+    BreedingTimeoutNotReached,
+    #[msg("Child strategy does not match the breeding request")]
+    InvalidChild,
+    #[msg("Invalid breeding config")]
+    InvalidBreedingConfig,
+    #[msg("All editions of this strategy have been printed")]
+    EditionSupplyExhausted,
+    #[msg("Strategy is already minted, its edition supply is fixed")]
+    EditionSupplyLocked,
+    #[msg("Not supported for this token standard")]
+    UnsupportedTokenStandard,
+    #[msg("Programmable NFTs need their token record accounts")]
+    MissingTokenRecord,
+    #[msg("Rule set doesn't match the collection config")]
+    InvalidRuleSet,
+    #[msg("Metadata accounts are required to change the URI")]
+    MissingMetadata,
 }
 
 #[event]
 pub struct CollectionInitialized {
     pub authority: Pubkey,
+    pub collection_mint: Pubkey,
     pub name: String,
     pub symbol: String,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct CollectionConfigMigrated {
+    pub authority: Pubkey,
+    pub collection_mint: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct StrategyNftMinted {
     pub mint: Pubkey,
     pub owner: Pubkey,
+    /// Wallet that paid rent and the mint fee, may differ from `owner`
+    pub payer: Pubkey,
     pub strategy_id: String,
-    pub genes_hash: String,
+    pub genes_hash: [u8; 32],
     pub archetype: String,
     pub generation: u32,
     pub fitness_score: u64,
     pub total_pnl: i64,
+    pub win_rate: u64,
+    pub trades_executed: u32,
+    pub sharpe_ratio_milli: i32,
+    pub max_drawdown_bps: u16,
+    pub avg_hold_time_secs: u32,
+    pub parent_a: Option<Pubkey>,
+    pub parent_b: Option<Pubkey>,
     pub mint_price: u64,
     pub timestamp: i64,
 }
 
+impl StrategyNftMinted {
+    pub fn from_attestation(
+        attestation: MintAttestation,
+        mint: Pubkey,
+        payer: Pubkey,
+        mint_price: u64,
+        timestamp: i64,
+    ) -> Self {
+        Self {
+            mint,
+            owner: attestation.owner,
+            payer,
+            strategy_id: attestation.strategy_id,
+            genes_hash: attestation.genes_hash,
+            archetype: attestation.archetype,
+            generation: attestation.generation,
+            fitness_score: attestation.fitness_score,
+            total_pnl: attestation.total_pnl,
+            win_rate: attestation.win_rate,
+            trades_executed: attestation.trades_executed,
+            sharpe_ratio_milli: attestation.sharpe_ratio_milli,
+            max_drawdown_bps: attestation.max_drawdown_bps,
+            avg_hold_time_secs: attestation.avg_hold_time_secs,
+            parent_a: attestation.parent_a,
+            parent_b: attestation.parent_b,
+            mint_price,
+            timestamp,
+        }
+    }
+}
+
+#[event]
+pub struct EditionMinted {
+    pub master_mint: Pubkey,
+    pub edition_mint: Pubkey,
+    pub strategy_id: String,
+    pub edition: u64,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MasterEditionEscrowed {
+    pub mint: Pubkey,
+    pub strategy_id: String,
+    pub depositor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MasterEditionReleased {
+    pub mint: Pubkey,
+    pub strategy_id: String,
+    pub depositor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EditionSupplySet {
+    pub strategy_id: String,
+    pub max_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EditionPriceUpdated {
+    pub old_price: u64,
+    pub new_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BreedingRequested {
+    pub request: Pubkey,
+    pub request_id: u64,
+    pub requester: Pubkey,
+    pub parent_a: Pubkey,
+    pub parent_b: Pubkey,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BreedingFulfilled {
+    pub request: Pubkey,
+    pub request_id: u64,
+    pub child_mint: Pubkey,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BreedingRefunded {
+    pub request: Pubkey,
+    pub request_id: u64,
+    pub requester: Pubkey,
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BreedingConfigUpdated {
+    pub breeding_fee_lamports: u64,
+    pub breeding_timeout_seconds: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StrategyDied {
+    pub mint: Pubkey,
+    pub strategy_id: String,
+    pub generation: u32,
+    pub fitness_score: u64,
+    pub total_pnl: i64,
+    pub win_rate: u64,
+    pub trades_executed: u32,
+    pub sharpe_ratio_milli: i32,
+    pub max_drawdown_bps: u16,
+    pub avg_hold_time_secs: u32,
+    pub fossil_uri: Option<String>,
+    pub died_at: i64,
+}
+
+#[event]
+pub struct StrategyNftBurned {
+    pub mint: Pubkey,
+    pub strategy_id: String,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StrategyOwnerChanged {
+    pub mint: Pubkey,
+    pub strategy_id: String,
+    pub old_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StrategyNftMigrated {
+    pub mint: Pubkey,
+    pub strategy_id: String,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct MintPriceUpdated {
     pub old_price: u64,
@@ -399,3 +3979,57 @@ pub struct AuthorityTransferred {
     pub new_authority: Pubkey,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct MetadataConfigUpdated {
+    pub name_prefix: String,
+    pub symbol: String,
+    pub base_uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoyaltyConfigUpdated {
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<CreatorShare>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StrategyRoyaltiesUpdated {
+    pub mint: Pubkey,
+    pub strategy_id: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<CreatorShare>,
+    pub is_override: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenStandardUpdated {
+    pub token_standard: NftStandard,
+    pub rule_set: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttestorUpdated {
+    pub old_attestor: Pubkey,
+    pub new_attestor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StrategyStatsUpdated {
+    pub mint: Pubkey,
+    pub strategy_id: String,
+    pub fitness_score: u64,
+    pub total_pnl: i64,
+    pub win_rate: u64,
+    pub trades_executed: u32,
+    pub sharpe_ratio_milli: i32,
+    pub max_drawdown_bps: u16,
+    pub avg_hold_time_secs: u32,
+    pub new_uri: Option<String>,
+    pub timestamp: i64,
+}
@@ -1,8 +1,29 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
 use anchor_lang::system_program;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{burn, mint_to, transfer, Burn, Mint, MintTo, Token, TokenAccount, Transfer},
+};
+use meta_attestation::{attestation_message, verify_ed25519_attestation, AttestationError};
 
 declare_id!("MetaTreasury11111111111111111111111111111111");
 
+/// Layout version written by `initialize_treasury` and `migrate_treasury`
+pub const TREASURY_VERSION: u8 = 1;
+/// Maximum number of recovery guardians stored on the treasury
+pub const MAX_GUARDIANS: usize = 7;
+/// Default delay between a recovery reaching guardian quorum and becoming executable
+pub const RECOVERY_TIMELOCK_SECONDS: i64 = 7 * 24 * 60 * 60;
+/// Shortest recovery timelock, so the multisig always has a window to veto
+pub const MIN_RECOVERY_TIMELOCK_SECONDS: i64 = 7 * 24 * 60 * 60;
+/// Default time an attested PnL report can be disputed before it is final
+pub const DEFAULT_CHALLENGE_PERIOD_SECONDS: i64 = 24 * 60 * 60;
+/// Default bond a challenger posts to dispute a PnL report
+pub const DEFAULT_CHALLENGE_BOND_LAMPORTS: u64 = 1_000_000_000;
+/// Smallest challenge bond, so disputes always cost the challenger something
+pub const MIN_CHALLENGE_BOND_LAMPORTS: u64 = 10_000_000;
+
 #[program]
 pub mod meta_treasury {
     use super::*;
@@ -16,6 +37,10 @@ pub mod meta_treasury {
         treasury.emergency_multisig = ctx.accounts.multisig.key();
         treasury.is_initialized = true;
         treasury.bump = ctx.bumps.treasury;
+        treasury.challenge_period = DEFAULT_CHALLENGE_PERIOD_SECONDS;
+        treasury.challenge_bond = DEFAULT_CHALLENGE_BOND_LAMPORTS;
+        treasury.recovery_timelock = RECOVERY_TIMELOCK_SECONDS;
+        treasury.version = TREASURY_VERSION;
 
         // Transfer SOL to treasury PDA
         if amount > 0 {
@@ -40,6 +65,124 @@ pub mod meta_treasury {
         Ok(())
     }
 
+    /// Grow a treasury created under an older layout to the current one (authority only)
+    ///
+    /// Unclaimed distributions must be tallied before they can be reserved, so the
+    /// migration runs in batches: each call passes the next distributions in id order
+    /// in `remaining_accounts`, and the running tally is kept in the
+    /// `["treasury_migration"]` account. The call that tallies the last distribution
+    /// grows the treasury, gives the new fields their defaults and closes the tally.
+    /// Until then the treasury keeps its old layout, which no other instruction can
+    /// load, so the distributions cannot change between batches.
+    pub fn migrate_treasury(ctx: Context<MigrateTreasury>) -> Result<()> {
+        let info = ctx.accounts.treasury.to_account_info();
+        require_keys_eq!(*info.owner, crate::ID, MetaTreasuryError::NotInitialized);
+
+        // Fields were only ever appended, so a zero-padded copy reads as the current layout
+        let new_len = 8 + TreasuryState::INIT_SPACE;
+        let mut data = info.try_borrow_data()?.to_vec();
+        require!(
+            data.len() >= 8 + 32 && data[..8] == <TreasuryState as Discriminator>::DISCRIMINATOR,
+            MetaTreasuryError::NotInitialized
+        );
+        // `authority` leads every layout of the account
+        require!(
+            data[8..40] == ctx.accounts.authority.key().to_bytes(),
+            MetaTreasuryError::Unauthorized
+        );
+        if data.len() < new_len {
+            data.resize(new_len, 0);
+        }
+
+        let mut treasury = TreasuryState::try_deserialize(&mut &data[..])?;
+        let from_version = treasury.version;
+        require!(from_version < TREASURY_VERSION, MetaTreasuryError::AlreadyMigrated);
+
+        if from_version == 0 {
+            let migration = &mut ctx.accounts.migration;
+            migration.bump = ctx.bumps.migration;
+
+            let first = migration.next_distribution;
+            let end = first.checked_add(ctx.remaining_accounts.len() as u64).unwrap();
+            require!(end <= treasury.distribution_count, MetaTreasuryError::InvalidDistribution);
+
+            for (offset, account) in ctx.remaining_accounts.iter().enumerate() {
+                let id = first + offset as u64;
+                let (expected, _) = Pubkey::find_program_address(
+                    &[b"distribution", id.to_le_bytes().as_ref()],
+                    &crate::ID,
+                );
+                require_keys_eq!(account.key(), expected, MetaTreasuryError::InvalidDistribution);
+                require_keys_eq!(*account.owner, crate::ID, MetaTreasuryError::InvalidDistribution);
+
+                let distribution = Distribution::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+                if !distribution.swept {
+                    let unclaimed = distribution
+                        .total_amount
+                        .checked_sub(distribution.claimed_amount)
+                        .unwrap();
+                    migration.outstanding = migration.outstanding.checked_add(unclaimed).unwrap();
+                }
+            }
+            migration.next_distribution = end;
+
+            if end < treasury.distribution_count {
+                emit!(TreasuryMigrationProgress {
+                    next_distribution: end,
+                    distribution_count: treasury.distribution_count,
+                    timestamp: Clock::get()?.unix_timestamp,
+                });
+                return Ok(());
+            }
+
+            // A bond of zero means the challenge window predates the account
+            if treasury.challenge_bond == 0 {
+                treasury.challenge_period = DEFAULT_CHALLENGE_PERIOD_SECONDS;
+                treasury.challenge_bond = DEFAULT_CHALLENGE_BOND_LAMPORTS;
+            }
+            treasury.recovery_timelock = RECOVERY_TIMELOCK_SECONDS;
+
+            // Pending profits used to be counted in `total_sol`
+            treasury.total_sol = treasury.total_sol.checked_sub(treasury.pending_profits).unwrap();
+            treasury.outstanding_distributions = migration.outstanding;
+        }
+
+        // Top up rent if the new layout needs more than the account holds
+        if info.data_len() < new_len {
+            let shortfall = Rent::get()?
+                .minimum_balance(new_len)
+                .saturating_sub(info.lamports());
+            if shortfall > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: ctx.accounts.authority.to_account_info(),
+                            to: info.clone(),
+                        },
+                    ),
+                    shortfall,
+                )?;
+            }
+            info.realloc(new_len, true)?;
+        }
+
+        treasury.version = TREASURY_VERSION;
+        treasury.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        ctx.accounts
+            .migration
+            .close(ctx.accounts.authority.to_account_info())?;
+
+        emit!(TreasuryMigrated {
+            from_version,
+            to_version: TREASURY_VERSION,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
     /// Add profits to the pool for later distribution
     pub fn add_profits(ctx: Context<AddProfits>, amount: u64) -> Result<()> {
         let treasury = &mut ctx.accounts.treasury;
@@ -72,6 +215,223 @@ pub mod meta_treasury {
         Ok(())
     }
 
+    /// Deposit protocol revenue (e.g. NFT mint fees) into the profit pool (permissionless)
+    pub fn deposit_revenue(ctx: Context<DepositRevenue>, amount: u64) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        require!(amount > 0, MetaTreasuryError::InvalidAmount);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: treasury.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        treasury.total_sol = treasury.total_sol.checked_add(amount).unwrap();
+        treasury.profit_pool = treasury.profit_pool.checked_add(amount).unwrap();
+        treasury.total_revenue = treasury.total_revenue.checked_add(amount).unwrap();
+
+        emit!(RevenueDeposited {
+            depositor: ctx.accounts.depositor.key(),
+            amount,
+            total_revenue: treasury.total_revenue,
+            new_profit_pool: treasury.profit_pool,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Submit profits backed by an engine-signed PnL report for one trading cycle
+    ///
+    /// The instruction immediately before this one must be an ed25519 program
+    /// instruction in which `engine_attestor` signs the Borsh-encoded `report`.
+    /// The profits stay pending, outside `total_sol`, until the challenge period ends undisputed.
+    pub fn add_attested_profits(ctx: Context<AddAttestedProfits>, report: PnlReport) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        require!(
+            treasury.engine_attestor != Pubkey::default(),
+            MetaTreasuryError::AttestorNotSet
+        );
+
+        verify_pnl_report_attestation(
+            &ctx.accounts.instructions_sysvar,
+            &treasury.engine_attestor,
+            &report,
+        )?;
+
+        // Losing cycles are recorded for the audit trail but move no funds
+        let amount = report.realized_pnl.max(0) as u64;
+        if amount > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: treasury.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+
+            treasury.pending_profits = treasury.pending_profits.checked_add(amount).unwrap();
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let record = &mut ctx.accounts.pnl_report;
+        record.report = report;
+        record.attestor = treasury.engine_attestor;
+        record.amount = amount;
+        record.reported_at = now;
+        record.challenge_ends_at = now.checked_add(treasury.challenge_period).unwrap();
+        record.status = ReportStatus::Pending;
+        record.bump = ctx.bumps.pnl_report;
+
+        emit!(PnlReportSubmitted {
+            cycle_id: report.cycle_id,
+            realized_pnl: report.realized_pnl,
+            trade_count: report.trade_count,
+            trades_hash: report.trades_hash,
+            amount,
+            challenge_ends_at: record.challenge_ends_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Move an undisputed report's profits into `profit_pool` after the challenge period (permissionless)
+    pub fn finalize_report(ctx: Context<FinalizeReport>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let record = &mut ctx.accounts.pnl_report;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(record.status == ReportStatus::Pending, MetaTreasuryError::ReportNotPending);
+        require!(now >= record.challenge_ends_at, MetaTreasuryError::ChallengePeriodActive);
+
+        release_pending_profits(treasury, record.amount);
+        record.status = ReportStatus::Finalized;
+
+        emit!(PnlReportFinalized {
+            cycle_id: record.report.cycle_id,
+            amount: record.amount,
+            new_profit_pool: treasury.profit_pool,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Dispute a pending report by posting the challenge bond
+    pub fn challenge_report(ctx: Context<ChallengeReport>) -> Result<()> {
+        let record = &mut ctx.accounts.pnl_report;
+        let bond = ctx.accounts.treasury.challenge_bond;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(record.status == ReportStatus::Pending, MetaTreasuryError::ReportNotPending);
+        require!(now < record.challenge_ends_at, MetaTreasuryError::ChallengePeriodEnded);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.challenger.to_account_info(),
+                    to: ctx.accounts.dispute.to_account_info(),
+                },
+            ),
+            bond,
+        )?;
+
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.pnl_report = record.key();
+        dispute.challenger = ctx.accounts.challenger.key();
+        dispute.bond = bond;
+        dispute.opened_at = now;
+        dispute.bump = ctx.bumps.dispute;
+
+        record.status = ReportStatus::Disputed;
+
+        emit!(PnlReportChallenged {
+            cycle_id: record.report.cycle_id,
+            challenger: dispute.challenger,
+            bond,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Settle a dispute (multisig only)
+    ///
+    /// If the report is upheld the challenger's bond is slashed into the insurance
+    /// reserve and the profits are released. Otherwise the bond is returned and the
+    /// report's pending amount is refunded to the authority.
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, report_valid: bool) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let record = &mut ctx.accounts.pnl_report;
+        let bond = ctx.accounts.dispute.bond;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(record.status == ReportStatus::Disputed, MetaTreasuryError::ReportNotDisputed);
+
+        if report_valid {
+            **ctx.accounts.dispute.to_account_info().try_borrow_mut_lamports()? -= bond;
+            **treasury.to_account_info().try_borrow_mut_lamports()? += bond;
+
+            treasury.total_sol = treasury.total_sol.checked_add(bond).unwrap();
+            treasury.insurance_reserve = treasury.insurance_reserve.checked_add(bond).unwrap();
+            release_pending_profits(treasury, record.amount);
+            record.status = ReportStatus::Finalized;
+        } else {
+            **treasury.to_account_info().try_borrow_mut_lamports()? -= record.amount;
+            **ctx.accounts.authority.to_account_info().try_borrow_mut_lamports()? += record.amount;
+
+            treasury.pending_profits = treasury.pending_profits.checked_sub(record.amount).unwrap();
+            record.status = ReportStatus::Rejected;
+        }
+
+        emit!(DisputeResolved {
+            cycle_id: record.report.cycle_id,
+            challenger: ctx.accounts.challenger.key(),
+            report_valid,
+            bond,
+            amount: record.amount,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Set the PnL challenge period and bond (multisig only)
+    pub fn set_challenge_params(
+        ctx: Context<UpdateMultisig>,
+        challenge_period: i64,
+        challenge_bond: u64,
+    ) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+
+        set_challenge_params_config(treasury, challenge_period, challenge_bond)
+    }
+
+    /// Set the engine key that signs PnL reports (multisig only)
+    pub fn set_engine_attestor(ctx: Context<UpdateMultisig>, attestor: Pubkey) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+
+        set_engine_attestor_config(treasury, attestor)
+    }
+
     /// Distribute profits proportionally to a holder
     pub fn distribute_profits(
         ctx: Context<DistributeProfits>,
@@ -117,11 +477,16 @@ pub mod meta_treasury {
     }
 
     /// Emergency withdrawal - requires multisig authority
+    ///
+    /// Only liquid vault SOL can leave: the profit pool, insurance reserve, unclaimed
+    /// distributions and SOL owed to queued withdrawals stay behind. Liquid SOL backs
+    /// the vault shares, so a withdrawal lowers NAV and is a loss to every depositor.
     pub fn withdraw_emergency(ctx: Context<WithdrawEmergency>, amount: u64) -> Result<()> {
         let treasury = &mut ctx.accounts.treasury;
         
         require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
-        require!(amount > 0 && amount <= treasury.total_sol, MetaTreasuryError::InsufficientFunds);
+        require!(amount > 0, MetaTreasuryError::InvalidAmount);
+        require!(amount <= treasury.liquid_sol(), MetaTreasuryError::InsufficientFunds);
 
         // Transfer from treasury PDA to destination
         **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? -= amount;
@@ -146,138 +511,2143 @@ pub mod meta_treasury {
         
         require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
 
-        let old_multisig = treasury.emergency_multisig;
-        treasury.emergency_multisig = new_multisig;
+        set_multisig(treasury, new_multisig)
+    }
 
-        emit!(MultisigUpdated {
-            old_multisig,
-            new_multisig,
+    /// Replace the guardian set used for multisig recovery (multisig only)
+    pub fn set_guardians(
+        ctx: Context<UpdateMultisig>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+        timelock: i64,
+    ) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        require!(guardians.len() <= MAX_GUARDIANS, MetaTreasuryError::InvalidGuardians);
+        require!(
+            threshold as usize <= guardians.len() && (threshold > 0 || guardians.is_empty()),
+            MetaTreasuryError::InvalidGuardians
+        );
+        require!(
+            timelock >= MIN_RECOVERY_TIMELOCK_SECONDS,
+            MetaTreasuryError::RecoveryTimelockTooShort
+        );
+        for (i, guardian) in guardians.iter().enumerate() {
+            require!(!guardians[..i].contains(guardian), MetaTreasuryError::InvalidGuardians);
+        }
+
+        treasury.guardians = guardians;
+        treasury.guardian_threshold = threshold;
+        treasury.recovery_timelock = timelock;
+
+        emit!(GuardiansUpdated {
+            guardians: treasury.guardians.clone(),
+            threshold,
+            timelock,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct InitializeTreasury<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + TreasuryState::INIT_SPACE,
-        seeds = [b"treasury"],
-        bump
-    )]
-    pub treasury: Account<'info, TreasuryState>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    /// CHECK: Multisig account for emergency operations
-    pub multisig: UncheckedAccount<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    /// Start replacing a lost emergency multisig (guardian only)
+    ///
+    /// Each proposal gets its own request account, so a stalled proposal never
+    /// blocks the guardians from opening another.
+    pub fn propose_recovery(ctx: Context<ProposeRecovery>, new_multisig: Pubkey) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let guardian = ctx.accounts.guardian.key();
+        let now = Clock::get()?.unix_timestamp;
 
-#[derive(Accounts)]
-pub struct AddProfits<'info> {
-    #[account(
-        mut,
-        seeds = [b"treasury"],
-        bump = treasury.bump
-    )]
-    pub treasury: Account<'info, TreasuryState>,
-    
-    #[account(mut, constraint = authority.key() == treasury.authority)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        require!(treasury.guardians.contains(&guardian), MetaTreasuryError::NotGuardian);
 
-#[derive(Accounts)]
-pub struct DistributeProfits<'info> {
-    #[account(
-        mut,
-        seeds = [b"treasury"],
-        bump = treasury.bump
-    )]
-    pub treasury: Account<'info, TreasuryState>,
-    
-    #[account(constraint = authority.key() == treasury.authority)]
-    pub authority: Signer<'info>,
-    
-    /// CHECK: Holder receiving profit distribution
-    #[account(mut)]
-    pub holder: UncheckedAccount<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+        let recovery = &mut ctx.accounts.recovery;
+        recovery.id = treasury.recovery_count;
+        recovery.proposer = guardian;
+        recovery.new_multisig = new_multisig;
+        recovery.approvals = vec![guardian];
+        recovery.proposed_at = now;
+        recovery.executable_at = 0;
+        recovery.bump = ctx.bumps.recovery;
+        recovery.start_timelock_if_quorum(treasury, now);
 
-#[derive(Accounts)]
-pub struct WithdrawEmergency<'info> {
-    #[account(
-        mut,
-        seeds = [b"treasury"],
-        bump = treasury.bump
-    )]
-    pub treasury: Account<'info, TreasuryState>,
-    
-    #[account(constraint = multisig.key() == treasury.emergency_multisig)]
-    pub multisig: Signer<'info>,
-    
-    /// CHECK: Destination for emergency withdrawal
-    #[account(mut)]
-    pub destination: UncheckedAccount<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+        treasury.recovery_count = treasury.recovery_count.checked_add(1).unwrap();
 
-#[derive(Accounts)]
-pub struct UpdateMultisig<'info> {
-    #[account(
-        mut,
-        seeds = [b"treasury"],
-        bump = treasury.bump
-    )]
-    pub treasury: Account<'info, TreasuryState>,
-    
-    #[account(constraint = multisig.key() == treasury.emergency_multisig)]
-    pub multisig: Signer<'info>,
-}
+        emit!(RecoveryProposed {
+            id: recovery.id,
+            proposer: guardian,
+            new_multisig,
+            executable_at: recovery.executable_at,
+            timestamp: now,
+        });
 
-#[account]
-#[derive(InitSpace)]
-pub struct TreasuryState {
-    pub authority: Pubkey,
-    pub emergency_multisig: Pubkey,
-    pub total_sol: u64,
-    pub profit_pool: u64,
-    pub is_initialized: bool,
-    pub bump: u8,
-}
+        Ok(())
+    }
 
-#[error_code]
-pub enum MetaTreasuryError {
-    #[msg("Treasury not initialized")]
-    NotInitialized,
-    #[msg("Invalid amount")]
-    InvalidAmount,
-    #[msg("No profits available for distribution")]
-    NoProfits,
-    #[msg("Invalid share percentage")]
-    InvalidShare,
-    #[msg("Insufficient funds in treasury")]
-    InsufficientFunds,
-}
+    /// Add a guardian approval to the pending recovery
+    pub fn approve_recovery(ctx: Context<ApproveRecovery>) -> Result<()> {
+        let treasury = &ctx.accounts.treasury;
+        let recovery = &mut ctx.accounts.recovery;
+        let guardian = ctx.accounts.guardian.key();
+        let now = Clock::get()?.unix_timestamp;
 
-#[event]
-pub struct TreasuryInitialized {
-    pub authority: Pubkey,
-    pub initial_amount: u64,
-    pub timestamp: i64,
-}
+        require!(treasury.guardians.contains(&guardian), MetaTreasuryError::NotGuardian);
+        require!(recovery.id >= treasury.recovery_floor, MetaTreasuryError::StaleRecovery);
+        require!(!recovery.approvals.contains(&guardian), MetaTreasuryError::AlreadyApproved);
 
-#[event]
+        recovery.approvals.push(guardian);
+        recovery.start_timelock_if_quorum(treasury, now);
+
+        emit!(RecoveryApproved {
+            id: recovery.id,
+            guardian,
+            approvals: recovery.approvals.len() as u8,
+            executable_at: recovery.executable_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel a pending recovery (current multisig only)
+    pub fn veto_recovery(ctx: Context<VetoRecovery>) -> Result<()> {
+        emit!(RecoveryVetoed {
+            id: ctx.accounts.recovery.id,
+            multisig: ctx.accounts.multisig.key(),
+            new_multisig: ctx.accounts.recovery.new_multisig,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Install the recovered multisig once quorum holds and the timelock has elapsed (permissionless)
+    pub fn execute_recovery(ctx: Context<ExecuteRecovery>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let recovery = &ctx.accounts.recovery;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        require!(recovery.id >= treasury.recovery_floor, MetaTreasuryError::StaleRecovery);
+
+        // Only approvals from guardians still in the set count
+        let approvals = recovery
+            .approvals
+            .iter()
+            .filter(|approver| treasury.guardians.contains(approver))
+            .count();
+
+        require!(
+            treasury.guardian_threshold > 0 && approvals >= treasury.guardian_threshold as usize,
+            MetaTreasuryError::RecoveryQuorumNotMet
+        );
+        require!(
+            recovery.executable_at > 0 && now >= recovery.executable_at,
+            MetaTreasuryError::TimelockNotElapsed
+        );
+
+        let old_multisig = treasury.emergency_multisig;
+        set_multisig(treasury, recovery.new_multisig)?;
+
+        emit!(RecoveryExecuted {
+            id: recovery.id,
+            old_multisig,
+            new_multisig: recovery.new_multisig,
+            approvals: approvals as u8,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Set aside profits for holder claims that expire at `expires_at`
+    pub fn create_distribution(
+        ctx: Context<CreateDistribution>,
+        amount: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        require!(amount > 0, MetaTreasuryError::InvalidAmount);
+        require!(amount <= treasury.profit_pool, MetaTreasuryError::InsufficientFunds);
+        require!(expires_at > now, MetaTreasuryError::InvalidExpiry);
+
+        let distribution = &mut ctx.accounts.distribution;
+        distribution.id = treasury.distribution_count;
+        distribution.total_amount = amount;
+        distribution.allocated_amount = 0;
+        distribution.claimed_amount = 0;
+        distribution.expires_at = expires_at;
+        distribution.swept = false;
+        distribution.bump = ctx.bumps.distribution;
+
+        treasury.profit_pool = treasury.profit_pool.checked_sub(amount).unwrap();
+        treasury.outstanding_distributions = treasury.outstanding_distributions.checked_add(amount).unwrap();
+        treasury.distribution_count = treasury.distribution_count.checked_add(1).unwrap();
+
+        emit!(DistributionCreated {
+            id: distribution.id,
+            amount,
+            expires_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Assign part of a distribution to a holder
+    pub fn allocate_claim(ctx: Context<AllocateClaim>, holder: Pubkey, amount: u64) -> Result<()> {
+        let distribution = &mut ctx.accounts.distribution;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(ctx.accounts.treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        require!(amount > 0, MetaTreasuryError::InvalidAmount);
+        require!(now < distribution.expires_at, MetaTreasuryError::DistributionExpired);
+
+        distribution.allocated_amount = distribution.allocated_amount.checked_add(amount).unwrap();
+        require!(
+            distribution.allocated_amount <= distribution.total_amount,
+            MetaTreasuryError::InsufficientFunds
+        );
+
+        let claim = &mut ctx.accounts.claim;
+        claim.distribution = distribution.key();
+        claim.holder = holder;
+        claim.amount = amount;
+        claim.claimed = false;
+        claim.bump = ctx.bumps.claim;
+
+        emit!(ClaimAllocated {
+            distribution_id: distribution.id,
+            holder,
+            amount,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Collect an allocated payout before its distribution expires; the claim rent goes to the holder
+    pub fn claim_distribution(ctx: Context<ClaimDistribution>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let distribution = &mut ctx.accounts.distribution;
+        let claim = &mut ctx.accounts.claim;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(now < distribution.expires_at, MetaTreasuryError::DistributionExpired);
+        require!(!claim.claimed, MetaTreasuryError::AlreadyClaimed);
+
+        **treasury.to_account_info().try_borrow_mut_lamports()? -= claim.amount;
+        **ctx.accounts.holder.to_account_info().try_borrow_mut_lamports()? += claim.amount;
+
+        claim.claimed = true;
+        distribution.claimed_amount = distribution.claimed_amount.checked_add(claim.amount).unwrap();
+        treasury.total_sol = treasury.total_sol.checked_sub(claim.amount).unwrap();
+        treasury.outstanding_distributions = treasury
+            .outstanding_distributions
+            .checked_sub(claim.amount)
+            .unwrap();
+
+        emit!(DistributionClaimed {
+            distribution_id: distribution.id,
+            holder: claim.holder,
+            amount: claim.amount,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Return the unclaimed remainder of an expired distribution and close it (permissionless)
+    pub fn sweep_expired(ctx: Context<SweepExpired>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let distribution = &mut ctx.accounts.distribution;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(now >= distribution.expires_at, MetaTreasuryError::DistributionNotExpired);
+        require!(!distribution.swept, MetaTreasuryError::AlreadySwept);
+
+        let reclaimed = distribution
+            .total_amount
+            .checked_sub(distribution.claimed_amount)
+            .unwrap();
+
+        treasury.outstanding_distributions = treasury
+            .outstanding_distributions
+            .checked_sub(reclaimed)
+            .unwrap();
+        if treasury.sweep_to_insurance {
+            treasury.insurance_reserve = treasury.insurance_reserve.checked_add(reclaimed).unwrap();
+        } else {
+            treasury.profit_pool = treasury.profit_pool.checked_add(reclaimed).unwrap();
+        }
+        distribution.swept = true;
+
+        emit!(DistributionSwept {
+            distribution_id: distribution.id,
+            reclaimed,
+            to_insurance: treasury.sweep_to_insurance,
+            profit_pool: treasury.profit_pool,
+            insurance_reserve: treasury.insurance_reserve,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Choose whether expired distributions return to `profit_pool` or the insurance reserve (multisig only)
+    pub fn set_sweep_destination(ctx: Context<UpdateMultisig>, to_insurance: bool) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+
+        set_sweep_destination_config(treasury, to_insurance)
+    }
+
+    /// Create the vault share mint and issue shares for the capital already in the treasury
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        require!(treasury.share_mint == Pubkey::default(), MetaTreasuryError::VaultAlreadyInitialized);
+
+        treasury.share_mint = ctx.accounts.share_mint.key();
+
+        // Seed capital is owned by the authority, so it is issued 1:1 as the first shares
+        let initial_shares = treasury.net_asset_value();
+        if initial_shares > 0 {
+            let bump = [treasury.bump];
+            let seeds = &[b"treasury".as_ref(), &bump];
+            let signer_seeds = &[&seeds[..]];
+
+            mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.share_mint.to_account_info(),
+                        to: ctx.accounts.authority_shares.to_account_info(),
+                        authority: treasury.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                initial_shares,
+            )?;
+        }
+
+        emit!(VaultInitialized {
+            share_mint: treasury.share_mint,
+            initial_shares,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Deposit SOL into the treasury in exchange for vault shares priced at NAV
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        require!(amount > 0, MetaTreasuryError::InvalidAmount);
+
+        let nav = treasury.net_asset_value();
+        let shares = shares_for_deposit(amount, nav, ctx.accounts.share_mint.supply)?;
+        require!(shares > 0, MetaTreasuryError::InvalidAmount);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: treasury.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let bump = [treasury.bump];
+        let seeds = &[b"treasury".as_ref(), &bump];
+        let signer_seeds = &[&seeds[..]];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.share_mint.to_account_info(),
+                    to: ctx.accounts.depositor_shares.to_account_info(),
+                    authority: treasury.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            shares,
+        )?;
+
+        treasury.total_sol = treasury.total_sol.checked_add(amount).unwrap();
+
+        emit!(Deposited {
+            depositor: ctx.accounts.depositor.key(),
+            amount,
+            shares,
+            nav_before: nav,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Burn vault shares and receive their pro rata SOL immediately
+    pub fn redeem(ctx: Context<Redeem>, shares: u64) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        require!(shares > 0, MetaTreasuryError::InvalidAmount);
+
+        let amount = lamports_for_shares(shares, treasury.net_asset_value(), ctx.accounts.share_mint.supply);
+        require!(amount > 0, MetaTreasuryError::InvalidAmount);
+        require!(amount <= treasury.liquid_sol(), MetaTreasuryError::InsufficientLiquidity);
+
+        burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.share_mint.to_account_info(),
+                    from: ctx.accounts.owner_shares.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            shares,
+        )?;
+
+        **treasury.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.owner.to_account_info().try_borrow_mut_lamports()? += amount;
+
+        treasury.total_sol = treasury.total_sol.checked_sub(amount).unwrap();
+
+        emit!(Redeemed {
+            owner: ctx.accounts.owner.key(),
+            shares,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Burn vault shares now and queue their SOL for payout once capital returns from trading
+    pub fn queue_withdrawal(ctx: Context<QueueWithdrawal>, shares: u64) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        require!(shares > 0, MetaTreasuryError::InvalidAmount);
+
+        let amount = lamports_for_shares(shares, treasury.net_asset_value(), ctx.accounts.share_mint.supply);
+        require!(amount > 0, MetaTreasuryError::InvalidAmount);
+
+        burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.share_mint.to_account_info(),
+                    from: ctx.accounts.owner_shares.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            shares,
+        )?;
+
+        let request = &mut ctx.accounts.withdrawal_request;
+        request.owner = ctx.accounts.owner.key();
+        request.queue_index = treasury.withdrawal_queue_tail;
+        request.shares = shares;
+        request.amount = amount;
+        request.requested_at = Clock::get()?.unix_timestamp;
+        request.bump = ctx.bumps.withdrawal_request;
+
+        treasury.queued_withdrawals = treasury.queued_withdrawals.checked_add(amount).unwrap();
+        treasury.withdrawal_queue_tail = treasury.withdrawal_queue_tail.checked_add(1).unwrap();
+
+        emit!(WithdrawalQueued {
+            owner: request.owner,
+            queue_index: request.queue_index,
+            shares,
+            amount,
+            timestamp: request.requested_at,
+        });
+
+        Ok(())
+    }
+
+    /// Pay out the withdrawal at the head of the queue (permissionless)
+    pub fn process_withdrawal(ctx: Context<ProcessWithdrawal>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let request = &ctx.accounts.withdrawal_request;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        require!(
+            request.queue_index == treasury.withdrawal_queue_head,
+            MetaTreasuryError::WithdrawalOutOfOrder
+        );
+        // The request is part of `queued_withdrawals`, so only reserved SOL is held back
+        require!(request.amount <= treasury.unreserved_sol(), MetaTreasuryError::InsufficientLiquidity);
+
+        **treasury.to_account_info().try_borrow_mut_lamports()? -= request.amount;
+        **ctx.accounts.owner.to_account_info().try_borrow_mut_lamports()? += request.amount;
+
+        treasury.total_sol = treasury.total_sol.checked_sub(request.amount).unwrap();
+        treasury.queued_withdrawals = treasury.queued_withdrawals.checked_sub(request.amount).unwrap();
+        treasury.withdrawal_queue_head = treasury.withdrawal_queue_head.checked_add(1).unwrap();
+
+        emit!(WithdrawalProcessed {
+            owner: request.owner,
+            queue_index: request.queue_index,
+            amount: request.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Move SOL out to the trading engine wallet; it keeps counting towards NAV
+    pub fn deploy_capital(ctx: Context<DeployCapital>, amount: u64) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        require!(amount > 0, MetaTreasuryError::InvalidAmount);
+        require!(amount <= treasury.liquid_sol(), MetaTreasuryError::InsufficientLiquidity);
+
+        **treasury.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.authority.to_account_info().try_borrow_mut_lamports()? += amount;
+
+        treasury.total_sol = treasury.total_sol.checked_sub(amount).unwrap();
+        treasury.deployed_capital = treasury.deployed_capital.checked_add(amount).unwrap();
+
+        emit!(CapitalDeployed {
+            amount,
+            deployed_capital: treasury.deployed_capital,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Return deployed SOL from the trading engine wallet to the treasury
+    pub fn return_capital(ctx: Context<ReturnCapital>, amount: u64) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        require!(
+            amount > 0 && amount <= treasury.deployed_capital,
+            MetaTreasuryError::InvalidAmount
+        );
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: treasury.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        treasury.deployed_capital = treasury.deployed_capital.checked_sub(amount).unwrap();
+        treasury.total_sol = treasury.total_sol.checked_add(amount).unwrap();
+
+        emit!(CapitalReturned {
+            amount,
+            deployed_capital: treasury.deployed_capital,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create the governance config and the vault that escrows $META voting deposits
+    pub fn initialize_governance(
+        ctx: Context<InitializeGovernance>,
+        params: GovernanceParams,
+    ) -> Result<()> {
+        require!(ctx.accounts.treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        params.validate()?;
+
+        let governance = &mut ctx.accounts.governance;
+        governance.meta_mint = ctx.accounts.meta_mint.key();
+        governance.vault = ctx.accounts.governance_vault.key();
+        governance.params = params;
+        governance.proposal_count = 0;
+        governance.bump = ctx.bumps.governance;
+
+        emit!(GovernanceInitialized {
+            meta_mint: governance.meta_mint,
+            params,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Deposit $META into the governance vault to gain voting weight
+    pub fn deposit_votes(ctx: Context<DepositVotes>, amount: u64) -> Result<()> {
+        require!(amount > 0, MetaTreasuryError::InvalidAmount);
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.voter_tokens.to_account_info(),
+                    to: ctx.accounts.governance_vault.to_account_info(),
+                    authority: ctx.accounts.voter.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let voter_record = &mut ctx.accounts.voter_record;
+        voter_record.voter = ctx.accounts.voter.key();
+        voter_record.deposited = voter_record.deposited.checked_add(amount).unwrap();
+        voter_record.bump = ctx.bumps.voter_record;
+
+        emit!(VotesDeposited {
+            voter: voter_record.voter,
+            amount,
+            total_deposited: voter_record.deposited,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw $META from the governance vault once no vote is locking it
+    pub fn withdraw_votes(ctx: Context<WithdrawVotes>, amount: u64) -> Result<()> {
+        let voter_record = &mut ctx.accounts.voter_record;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            amount > 0 && amount <= voter_record.deposited,
+            MetaTreasuryError::InvalidAmount
+        );
+        require!(now >= voter_record.locked_until, MetaTreasuryError::VotesLocked);
+
+        let seeds = &[b"governance".as_ref(), &[ctx.accounts.governance.bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.governance_vault.to_account_info(),
+                    to: ctx.accounts.voter_tokens.to_account_info(),
+                    authority: ctx.accounts.governance.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        voter_record.deposited = voter_record.deposited.checked_sub(amount).unwrap();
+
+        emit!(VotesWithdrawn {
+            voter: voter_record.voter,
+            amount,
+            total_deposited: voter_record.deposited,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Open a proposal to change treasury parameters
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: GovernanceAction) -> Result<()> {
+        let governance = &mut ctx.accounts.governance;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            ctx.accounts.voter_record.deposited >= governance.params.proposal_min_deposit,
+            MetaTreasuryError::InsufficientVotingWeight
+        );
+        if let GovernanceAction::UpdateGovernanceParams { params } = &action {
+            params.validate()?;
+        }
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.id = governance.proposal_count;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.action = action;
+        proposal.votes_for = 0;
+        proposal.votes_against = 0;
+        proposal.voting_ends_at = now.checked_add(governance.params.voting_period).unwrap();
+        proposal.executable_at = 0;
+        proposal.state = ProposalState::Voting;
+        proposal.bump = ctx.bumps.proposal;
+
+        governance.proposal_count = governance.proposal_count.checked_add(1).unwrap();
+
+        emit!(ProposalCreated {
+            id: proposal.id,
+            proposer: proposal.proposer,
+            action: proposal.action.clone(),
+            voting_ends_at: proposal.voting_ends_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Vote on an open proposal with the full deposited $META balance
+    pub fn cast_vote(ctx: Context<CastVote>, support: bool) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        let voter_record = &mut ctx.accounts.voter_record;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(proposal.state == ProposalState::Voting, MetaTreasuryError::ProposalNotVoting);
+        require!(now < proposal.voting_ends_at, MetaTreasuryError::VotingClosed);
+        require!(voter_record.deposited > 0, MetaTreasuryError::InsufficientVotingWeight);
+
+        let weight = voter_record.deposited;
+        if support {
+            proposal.votes_for = proposal.votes_for.checked_add(weight).unwrap();
+        } else {
+            proposal.votes_against = proposal.votes_against.checked_add(weight).unwrap();
+        }
+
+        // Deposits stay in the vault until voting ends so the same tokens can't vote twice
+        voter_record.locked_until = voter_record.locked_until.max(proposal.voting_ends_at);
+
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.voter = voter_record.voter;
+        vote_record.weight = weight;
+        vote_record.support = support;
+        vote_record.bump = ctx.bumps.vote_record;
+
+        emit!(VoteCast {
+            proposal_id: proposal.id,
+            voter: vote_record.voter,
+            weight,
+            support,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Tally a proposal after voting ends and start the timelock if it passed (permissionless)
+    pub fn queue_proposal(ctx: Context<QueueProposal>) -> Result<()> {
+        let params = ctx.accounts.governance.params;
+        let proposal = &mut ctx.accounts.proposal;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(proposal.state == ProposalState::Voting, MetaTreasuryError::ProposalNotVoting);
+        require!(now >= proposal.voting_ends_at, MetaTreasuryError::VotingStillOpen);
+
+        let total_votes = proposal.votes_for.checked_add(proposal.votes_against).unwrap();
+        let passed = total_votes >= params.quorum_votes
+            && (proposal.votes_for as u128).checked_mul(10000).unwrap()
+                >= (total_votes as u128).checked_mul(params.threshold_bps as u128).unwrap();
+
+        if passed {
+            proposal.state = ProposalState::Queued;
+            proposal.executable_at = now.checked_add(params.timelock_delay).unwrap();
+        } else {
+            proposal.state = ProposalState::Defeated;
+        }
+
+        emit!(ProposalQueued {
+            id: proposal.id,
+            passed,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            executable_at: proposal.executable_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Apply a queued proposal once its timelock has elapsed (permissionless)
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let governance = &mut ctx.accounts.governance;
+        let proposal = &mut ctx.accounts.proposal;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(treasury.is_initialized, MetaTreasuryError::NotInitialized);
+        require!(proposal.state == ProposalState::Queued, MetaTreasuryError::ProposalNotQueued);
+        require!(now >= proposal.executable_at, MetaTreasuryError::TimelockNotElapsed);
+
+        match proposal.action {
+            GovernanceAction::UpdateMultisig { new_multisig } => {
+                set_multisig(treasury, new_multisig)?;
+            }
+            GovernanceAction::UpdateAuthority { new_authority } => {
+                set_authority(treasury, new_authority)?;
+            }
+            GovernanceAction::UpdateGovernanceParams { params } => {
+                params.validate()?;
+                governance.params = params;
+            }
+            GovernanceAction::UpdateSweepDestination { to_insurance } => {
+                set_sweep_destination_config(treasury, to_insurance)?;
+            }
+            GovernanceAction::UpdateEngineAttestor { attestor } => {
+                set_engine_attestor_config(treasury, attestor)?;
+            }
+            GovernanceAction::UpdateChallengeParams {
+                challenge_period,
+                challenge_bond,
+            } => {
+                set_challenge_params_config(treasury, challenge_period, challenge_bond)?;
+            }
+        }
+
+        proposal.state = ProposalState::Executed;
+
+        emit!(ProposalExecuted {
+            id: proposal.id,
+            action: proposal.action.clone(),
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
+
+/// Config setter shared by `update_multisig`, `execute_recovery` and governance execution
+fn set_multisig(treasury: &mut TreasuryState, new_multisig: Pubkey) -> Result<()> {
+    let old_multisig = treasury.emergency_multisig;
+    treasury.emergency_multisig = new_multisig;
+    // Pending recoveries were proposed against the old multisig
+    treasury.recovery_floor = treasury.recovery_count;
+
+    emit!(MultisigUpdated {
+        old_multisig,
+        new_multisig,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Config setter for the treasury authority, reachable through governance
+fn set_authority(treasury: &mut TreasuryState, new_authority: Pubkey) -> Result<()> {
+    let old_authority = treasury.authority;
+    treasury.authority = new_authority;
+
+    emit!(AuthorityUpdated {
+        old_authority,
+        new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Config setter for where `sweep_expired` sends unclaimed funds, reachable through governance
+fn set_sweep_destination_config(treasury: &mut TreasuryState, to_insurance: bool) -> Result<()> {
+    treasury.sweep_to_insurance = to_insurance;
+
+    emit!(SweepDestinationUpdated {
+        to_insurance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Config setter for the PnL report signing key, reachable through governance
+fn set_engine_attestor_config(treasury: &mut TreasuryState, attestor: Pubkey) -> Result<()> {
+    let old_attestor = treasury.engine_attestor;
+    treasury.engine_attestor = attestor;
+
+    emit!(EngineAttestorUpdated {
+        old_attestor,
+        new_attestor: attestor,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Config setter for the PnL report challenge window, reachable through governance
+fn set_challenge_params_config(
+    treasury: &mut TreasuryState,
+    challenge_period: i64,
+    challenge_bond: u64,
+) -> Result<()> {
+    require!(
+        challenge_period >= 0 && challenge_bond >= MIN_CHALLENGE_BOND_LAMPORTS,
+        MetaTreasuryError::InvalidChallengeParams
+    );
+
+    treasury.challenge_period = challenge_period;
+    treasury.challenge_bond = challenge_bond;
+
+    emit!(ChallengeParamsUpdated {
+        challenge_period,
+        challenge_bond,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Move a settled report's amount from `pending_profits` into `total_sol` and `profit_pool`
+fn release_pending_profits(treasury: &mut TreasuryState, amount: u64) {
+    treasury.pending_profits = treasury.pending_profits.checked_sub(amount).unwrap();
+    treasury.total_sol = treasury.total_sol.checked_add(amount).unwrap();
+    treasury.profit_pool = treasury.profit_pool.checked_add(amount).unwrap();
+}
+
+/// Check that the previous instruction is the engine's ed25519 signature over `report`
+fn verify_pnl_report_attestation(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    report: &PnlReport,
+) -> Result<()> {
+    let message = attestation_message(&crate::ID, "PnlReport", &report.try_to_vec()?);
+    verify_ed25519_attestation(instructions_sysvar, signer, &message).map_err(|err| match err {
+        AttestationError::Missing => MetaTreasuryError::MissingAttestation.into(),
+        AttestationError::Invalid => MetaTreasuryError::InvalidAttestation.into(),
+    })
+}
+
+/// Shares minted for a deposit of `amount` lamports against the current NAV and share supply
+fn shares_for_deposit(amount: u64, nav: u64, supply: u64) -> Result<u64> {
+    // With no shares outstanding the first depositor would be handed the existing NAV
+    if supply == 0 {
+        require!(nav == 0, MetaTreasuryError::NavWithoutShares);
+        return Ok(amount);
+    }
+    // With shares but no NAV the existing holders would claim part of every deposit
+    require!(nav > 0, MetaTreasuryError::SharesWithoutNav);
+
+    let shares = (amount as u128)
+        .checked_mul(supply as u128)
+        .ok_or(MetaTreasuryError::MathOverflow)?
+        .checked_div(nav as u128)
+        .ok_or(MetaTreasuryError::MathOverflow)?;
+    u64::try_from(shares).map_err(|_| MetaTreasuryError::MathOverflow.into())
+}
+
+/// Lamports owed for redeeming `shares` against the current NAV and share supply
+fn lamports_for_shares(shares: u64, nav: u64, supply: u64) -> u64 {
+    if supply == 0 {
+        return 0;
+    }
+
+    (shares as u128)
+        .checked_mul(nav as u128)
+        .unwrap()
+        .checked_div(supply as u128)
+        .unwrap() as u64
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TreasuryState::INIT_SPACE,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Multisig account for emergency operations
+    pub multisig: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTreasury<'info> {
+    /// CHECK: Older-layout `TreasuryState`; owner, discriminator and authority are checked in the handler
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TreasuryMigration::INIT_SPACE,
+        seeds = [b"treasury_migration"],
+        bump
+    )]
+    pub migration: Account<'info, TreasuryMigration>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddProfits<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+    
+    #[account(mut, constraint = authority.key() == treasury.authority)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositRevenue<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(report: PnlReport)]
+pub struct AddAttestedProfits<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PnlReportRecord::INIT_SPACE,
+        seeds = [b"pnl_report", report.cycle_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pnl_report: Account<'info, PnlReportRecord>,
+
+    #[account(mut, constraint = authority.key() == treasury.authority)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Instructions sysvar, used to read the preceding ed25519 instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeReport<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        seeds = [b"pnl_report", pnl_report.report.cycle_id.to_le_bytes().as_ref()],
+        bump = pnl_report.bump
+    )]
+    pub pnl_report: Account<'info, PnlReportRecord>,
+}
+
+#[derive(Accounts)]
+pub struct ChallengeReport<'info> {
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        seeds = [b"pnl_report", pnl_report.report.cycle_id.to_le_bytes().as_ref()],
+        bump = pnl_report.bump
+    )]
+    pub pnl_report: Account<'info, PnlReportRecord>,
+
+    #[account(
+        init,
+        payer = challenger,
+        space = 8 + PnlDispute::INIT_SPACE,
+        seeds = [b"dispute", pnl_report.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, PnlDispute>,
+
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        seeds = [b"pnl_report", pnl_report.report.cycle_id.to_le_bytes().as_ref()],
+        bump = pnl_report.bump
+    )]
+    pub pnl_report: Account<'info, PnlReportRecord>,
+
+    #[account(
+        mut,
+        close = challenger,
+        seeds = [b"dispute", pnl_report.key().as_ref()],
+        bump = dispute.bump,
+        has_one = challenger
+    )]
+    pub dispute: Account<'info, PnlDispute>,
+
+    #[account(constraint = multisig.key() == treasury.emergency_multisig)]
+    pub multisig: Signer<'info>,
+
+    /// CHECK: Challenger who posted the bond, receives the dispute account rent
+    #[account(mut)]
+    pub challenger: UncheckedAccount<'info>,
+
+    /// CHECK: Treasury authority, refunded the pending amount of a rejected report
+    #[account(mut, address = treasury.authority)]
+    pub authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeProfits<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+    
+    #[account(constraint = authority.key() == treasury.authority)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Holder receiving profit distribution
+    #[account(mut)]
+    pub holder: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawEmergency<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+    
+    #[account(constraint = multisig.key() == treasury.emergency_multisig)]
+    pub multisig: Signer<'info>,
+    
+    /// CHECK: Destination for emergency withdrawal
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+    
+    #[account(constraint = multisig.key() == treasury.emergency_multisig)]
+    pub multisig: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeRecovery<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        init,
+        payer = guardian,
+        space = 8 + RecoveryRequest::INIT_SPACE,
+        seeds = [b"recovery", treasury.recovery_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub recovery: Account<'info, RecoveryRequest>,
+
+    #[account(mut)]
+    pub guardian: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveRecovery<'info> {
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        seeds = [b"recovery", recovery.id.to_le_bytes().as_ref()],
+        bump = recovery.bump
+    )]
+    pub recovery: Account<'info, RecoveryRequest>,
+
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VetoRecovery<'info> {
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"recovery", recovery.id.to_le_bytes().as_ref()],
+        bump = recovery.bump,
+        has_one = proposer
+    )]
+    pub recovery: Account<'info, RecoveryRequest>,
+
+    #[account(constraint = multisig.key() == treasury.emergency_multisig)]
+    pub multisig: Signer<'info>,
+
+    /// CHECK: Guardian who opened the recovery, refunded the request rent
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"recovery", recovery.id.to_le_bytes().as_ref()],
+        bump = recovery.bump,
+        has_one = proposer
+    )]
+    pub recovery: Account<'info, RecoveryRequest>,
+
+    /// CHECK: Guardian who opened the recovery, refunded the request rent
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateDistribution<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Distribution::INIT_SPACE,
+        seeds = [b"distribution", treasury.distribution_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub distribution: Account<'info, Distribution>,
+
+    #[account(mut, constraint = authority.key() == treasury.authority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(holder: Pubkey)]
+pub struct AllocateClaim<'info> {
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        seeds = [b"distribution", distribution.id.to_le_bytes().as_ref()],
+        bump = distribution.bump
+    )]
+    pub distribution: Account<'info, Distribution>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DistributionClaim::INIT_SPACE,
+        seeds = [b"claim", distribution.key().as_ref(), holder.as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, DistributionClaim>,
+
+    #[account(mut, constraint = authority.key() == treasury.authority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimDistribution<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        seeds = [b"distribution", distribution.id.to_le_bytes().as_ref()],
+        bump = distribution.bump
+    )]
+    pub distribution: Account<'info, Distribution>,
+
+    #[account(
+        mut,
+        close = holder,
+        seeds = [b"claim", distribution.key().as_ref(), holder.key().as_ref()],
+        bump = claim.bump,
+        has_one = holder
+    )]
+    pub claim: Account<'info, DistributionClaim>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SweepExpired<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"distribution", distribution.id.to_le_bytes().as_ref()],
+        bump = distribution.bump
+    )]
+    pub distribution: Account<'info, Distribution>,
+
+    /// CHECK: Treasury authority, paid the distribution rent and is refunded it
+    #[account(mut, address = treasury.authority)]
+    pub authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = 9,
+        mint::authority = treasury,
+        seeds = [b"share_mint"],
+        bump
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = share_mint,
+        associated_token::authority = authority,
+    )]
+    pub authority_shares: Account<'info, TokenAccount>,
+
+    #[account(mut, constraint = authority.key() == treasury.authority)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(mut, address = treasury.share_mint @ MetaTreasuryError::InvalidShareMint)]
+    pub share_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = depositor,
+    )]
+    pub depositor_shares: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Redeem<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(mut, address = treasury.share_mint @ MetaTreasuryError::InvalidShareMint)]
+    pub share_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = owner,
+    )]
+    pub owner_shares: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct QueueWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + WithdrawalRequest::INIT_SPACE,
+        seeds = [b"withdrawal", treasury.withdrawal_queue_tail.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    #[account(mut, address = treasury.share_mint @ MetaTreasuryError::InvalidShareMint)]
+    pub share_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = owner,
+    )]
+    pub owner_shares: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProcessWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"withdrawal", withdrawal_request.queue_index.to_le_bytes().as_ref()],
+        bump = withdrawal_request.bump,
+        has_one = owner
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+
+    /// CHECK: Owner of the queued withdrawal, receives the payout and the request rent
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DeployCapital<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(mut, constraint = authority.key() == treasury.authority)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReturnCapital<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(mut, constraint = authority.key() == treasury.authority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GovernanceConfig::INIT_SPACE,
+        seeds = [b"governance"],
+        bump
+    )]
+    pub governance: Account<'info, GovernanceConfig>,
+
+    pub meta_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = meta_mint,
+        token::authority = governance,
+        seeds = [b"governance_vault"],
+        bump
+    )]
+    pub governance_vault: Account<'info, TokenAccount>,
+
+    #[account(mut, constraint = authority.key() == treasury.authority)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositVotes<'info> {
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, GovernanceConfig>,
+
+    #[account(mut, address = governance.vault)]
+    pub governance_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = 8 + VoterRecord::INIT_SPACE,
+        seeds = [b"voter", voter.key().as_ref()],
+        bump
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+
+    #[account(
+        mut,
+        token::mint = governance.meta_mint,
+        token::authority = voter,
+    )]
+    pub voter_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawVotes<'info> {
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, GovernanceConfig>,
+
+    #[account(mut, address = governance.vault)]
+    pub governance_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"voter", voter.key().as_ref()],
+        bump = voter_record.bump
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+
+    #[account(
+        mut,
+        token::mint = governance.meta_mint,
+        token::authority = voter,
+    )]
+    pub voter_tokens: Account<'info, TokenAccount>,
+
+    pub voter: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, GovernanceConfig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::INIT_SPACE,
+        seeds = [b"proposal", governance.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [b"voter", proposer.key().as_ref()],
+        bump = voter_record.bump
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"voter", voter.key().as_ref()],
+        bump = voter_record.bump
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteRecord::INIT_SPACE,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct QueueProposal<'info> {
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, GovernanceConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, TreasuryState>,
+
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, GovernanceConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TreasuryState {
+    pub authority: Pubkey,
+    pub emergency_multisig: Pubkey,
+    pub total_sol: u64,
+    pub profit_pool: u64,
+    pub is_initialized: bool,
+    pub bump: u8,
+    /// Vault share token, `Pubkey::default()` until `initialize_vault`
+    pub share_mint: Pubkey,
+    /// SOL sent to the trading engine that still counts towards NAV
+    pub deployed_capital: u64,
+    /// SOL owed to queued withdrawals whose shares are already burned
+    pub queued_withdrawals: u64,
+    pub withdrawal_queue_head: u64,
+    pub withdrawal_queue_tail: u64,
+    /// Keys that can jointly replace a lost `emergency_multisig`
+    #[max_len(MAX_GUARDIANS)]
+    pub guardians: Vec<Pubkey>,
+    pub guardian_threshold: u8,
+    pub distribution_count: u64,
+    /// Reclaimed funds held back from distribution to cover losses
+    pub insurance_reserve: u64,
+    /// Where `sweep_expired` returns unclaimed funds
+    pub sweep_to_insurance: bool,
+    /// Engine key whose ed25519 signature `add_attested_profits` requires
+    pub engine_attestor: Pubkey,
+    /// Attested profits still inside their challenge period, held outside `total_sol`
+    pub pending_profits: u64,
+    /// Seconds an attested report can be disputed
+    pub challenge_period: i64,
+    /// Lamports a challenger must post to dispute a report
+    pub challenge_bond: u64,
+    /// Lifetime protocol revenue booked through `deposit_revenue`
+    pub total_revenue: u64,
+    /// Seconds between a recovery reaching quorum and becoming executable
+    pub recovery_timelock: i64,
+    /// Number of recovery proposals opened, used to derive each request's address
+    pub recovery_count: u64,
+    /// Proposals with a lower id were opened before the last multisig change
+    pub recovery_floor: u64,
+    /// SOL set aside by `create_distribution` that is not yet claimed or swept
+    pub outstanding_distributions: u64,
+    /// Layout version, see `TREASURY_VERSION`
+    pub version: u8,
+}
+
+impl TreasuryState {
+    /// SOL in `total_sol` owed outside the vault: undistributed profits, the insurance
+    /// reserve and unclaimed distributions
+    pub fn reserved_sol(&self) -> u64 {
+        self.profit_pool
+            .checked_add(self.insurance_reserve)
+            .unwrap()
+            .checked_add(self.outstanding_distributions)
+            .unwrap()
+    }
+
+    /// SOL held by the treasury that belongs to the vault
+    pub fn unreserved_sol(&self) -> u64 {
+        self.total_sol.saturating_sub(self.reserved_sol())
+    }
+
+    /// Net asset value backing the vault shares
+    pub fn net_asset_value(&self) -> u64 {
+        self.unreserved_sol()
+            .checked_add(self.deployed_capital)
+            .unwrap()
+            .saturating_sub(self.queued_withdrawals)
+    }
+
+    /// Vault SOL held by the treasury that is not owed to queued withdrawals
+    pub fn liquid_sol(&self) -> u64 {
+        self.unreserved_sol().saturating_sub(self.queued_withdrawals)
+    }
+}
+
+/// Running tally of a batched `migrate_treasury`, closed when the migration completes
+#[account]
+#[derive(InitSpace)]
+pub struct TreasuryMigration {
+    /// Id of the next distribution to tally
+    pub next_distribution: u64,
+    /// Unclaimed, unswept SOL across the distributions tallied so far
+    pub outstanding: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawalRequest {
+    pub owner: Pubkey,
+    pub queue_index: u64,
+    pub shares: u64,
+    pub amount: u64,
+    pub requested_at: i64,
+    pub bump: u8,
+}
+
+/// Engine-signed summary of one trading cycle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct PnlReport {
+    pub cycle_id: u64,
+    /// Realized PnL in lamports; only positive values are added to `profit_pool`
+    pub realized_pnl: i64,
+    pub trade_count: u32,
+    /// sha256 over the cycle's trades in the off-chain ledger
+    pub trades_hash: [u8; 32],
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PnlReportRecord {
+    pub report: PnlReport,
+    pub attestor: Pubkey,
+    /// Lamports deposited with the report, held in `pending_profits` until settled
+    pub amount: u64,
+    pub reported_at: i64,
+    pub challenge_ends_at: i64,
+    pub status: ReportStatus,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum ReportStatus {
+    Pending,
+    Disputed,
+    Finalized,
+    Rejected,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PnlDispute {
+    pub pnl_report: Pubkey,
+    pub challenger: Pubkey,
+    pub bond: u64,
+    pub opened_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Distribution {
+    pub id: u64,
+    pub total_amount: u64,
+    pub allocated_amount: u64,
+    pub claimed_amount: u64,
+    pub expires_at: i64,
+    pub swept: bool,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct DistributionClaim {
+    pub distribution: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RecoveryRequest {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub new_multisig: Pubkey,
+    #[max_len(MAX_GUARDIANS)]
+    pub approvals: Vec<Pubkey>,
+    pub proposed_at: i64,
+    /// Zero until the guardian quorum is reached
+    pub executable_at: i64,
+    pub bump: u8,
+}
+
+impl RecoveryRequest {
+    fn start_timelock_if_quorum(&mut self, treasury: &TreasuryState, now: i64) {
+        let threshold = treasury.guardian_threshold as usize;
+        if self.executable_at == 0 && threshold > 0 && self.approvals.len() >= threshold {
+            self.executable_at = now.checked_add(treasury.recovery_timelock).unwrap();
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct GovernanceConfig {
+    pub meta_mint: Pubkey,
+    pub vault: Pubkey,
+    pub params: GovernanceParams,
+    pub proposal_count: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct GovernanceParams {
+    /// Minimum $META (for + against) that must vote for a result to count
+    pub quorum_votes: u64,
+    /// Share of cast votes that must be in favour, in basis points
+    pub threshold_bps: u16,
+    /// Deposited $META required to open a proposal
+    pub proposal_min_deposit: u64,
+    /// Seconds a proposal stays open for voting
+    pub voting_period: i64,
+    /// Seconds between a proposal passing and becoming executable
+    pub timelock_delay: i64,
+}
+
+impl GovernanceParams {
+    pub fn validate(&self) -> Result<()> {
+        // A zero quorum or deposit would let a single token open and pass any proposal
+        require!(
+            self.quorum_votes > 0 && self.proposal_min_deposit > 0,
+            MetaTreasuryError::InvalidGovernanceParams
+        );
+        require!(
+            self.threshold_bps > 0 && self.threshold_bps <= 10000,
+            MetaTreasuryError::InvalidGovernanceParams
+        );
+        require!(
+            self.voting_period > 0 && self.timelock_delay >= 0,
+            MetaTreasuryError::InvalidGovernanceParams
+        );
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct VoterRecord {
+    pub voter: Pubkey,
+    pub deposited: u64,
+    /// Deposits can't be withdrawn before the end of the latest vote they were used in
+    pub locked_until: i64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: GovernanceAction,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub voting_ends_at: i64,
+    pub executable_at: i64,
+    pub state: ProposalState,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub weight: u64,
+    pub support: bool,
+    pub bump: u8,
+}
+
+/// Treasury config change carried out by `execute_proposal`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub enum GovernanceAction {
+    UpdateMultisig { new_multisig: Pubkey },
+    UpdateAuthority { new_authority: Pubkey },
+    UpdateGovernanceParams { params: GovernanceParams },
+    UpdateSweepDestination { to_insurance: bool },
+    UpdateEngineAttestor { attestor: Pubkey },
+    UpdateChallengeParams { challenge_period: i64, challenge_bond: u64 },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum ProposalState {
+    Voting,
+    Queued,
+    Defeated,
+    Executed,
+}
+
+#[error_code]
+pub enum MetaTreasuryError {
+    #[msg("Treasury not initialized")]
+    NotInitialized,
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("No profits available for distribution")]
+    NoProfits,
+    #[msg("Invalid share percentage")]
+    InvalidShare,
+    #[msg("Insufficient funds in treasury")]
+    InsufficientFunds,
+    #[msg("Vault already initialized")]
+    VaultAlreadyInitialized,
+    #[msg("Share mint does not match the treasury vault")]
+    InvalidShareMint,
+    #[msg("Not enough liquid SOL in the treasury")]
+    InsufficientLiquidity,
+    #[msg("Withdrawals must be processed in queue order")]
+    WithdrawalOutOfOrder,
+    #[msg("Invalid governance parameters")]
+    InvalidGovernanceParams,
+    #[msg("Not enough deposited $META")]
+    InsufficientVotingWeight,
+    #[msg("Deposited $META is locked by an active vote")]
+    VotesLocked,
+    #[msg("Proposal is not open for voting")]
+    ProposalNotVoting,
+    #[msg("Voting period has ended")]
+    VotingClosed,
+    #[msg("Voting period has not ended")]
+    VotingStillOpen,
+    #[msg("Proposal is not queued for execution")]
+    ProposalNotQueued,
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Invalid guardian set or threshold")]
+    InvalidGuardians,
+    #[msg("Signer is not a guardian")]
+    NotGuardian,
+    #[msg("Guardian already approved this recovery")]
+    AlreadyApproved,
+    #[msg("Recovery does not have enough guardian approvals")]
+    RecoveryQuorumNotMet,
+    #[msg("Recovery was proposed before the last multisig change")]
+    StaleRecovery,
+    #[msg("Recovery timelock is shorter than the minimum")]
+    RecoveryTimelockTooShort,
+    #[msg("Expiry must be in the future")]
+    InvalidExpiry,
+    #[msg("Distribution has expired")]
+    DistributionExpired,
+    #[msg("Distribution has not expired yet")]
+    DistributionNotExpired,
+    #[msg("Claim already paid out")]
+    AlreadyClaimed,
+    #[msg("Distribution already swept")]
+    AlreadySwept,
+    #[msg("Engine attestor not set")]
+    AttestorNotSet,
+    #[msg("Missing ed25519 attestation instruction")]
+    MissingAttestation,
+    #[msg("Attestation does not match the expected signer or message")]
+    InvalidAttestation,
+    #[msg("Report is not pending")]
+    ReportNotPending,
+    #[msg("Report is not disputed")]
+    ReportNotDisputed,
+    #[msg("Challenge period is still active")]
+    ChallengePeriodActive,
+    #[msg("Challenge period has ended")]
+    ChallengePeriodEnded,
+    #[msg("Invalid challenge parameters")]
+    InvalidChallengeParams,
+    #[msg("Vault holds assets but has no shares to price them against")]
+    NavWithoutShares,
+    #[msg("Vault has shares outstanding but no assets to price them against")]
+    SharesWithoutNav,
+    #[msg("Signer is not the treasury authority")]
+    Unauthorized,
+    #[msg("Treasury already uses the current layout")]
+    AlreadyMigrated,
+    #[msg("Distribution accounts do not match the treasury's distributions")]
+    InvalidDistribution,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+}
+
+#[event]
+pub struct TreasuryInitialized {
+    pub authority: Pubkey,
+    pub initial_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryMigrated {
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryMigrationProgress {
+    pub next_distribution: u64,
+    pub distribution_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
 pub struct ProfitsAdded {
     pub amount: u64,
     pub new_total: u64,
@@ -309,3 +2679,266 @@ pub struct MultisigUpdated {
     pub new_multisig: Pubkey,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct VaultInitialized {
+    pub share_mint: Pubkey,
+    pub initial_shares: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Deposited {
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub nav_before: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Redeemed {
+    pub owner: Pubkey,
+    pub shares: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalQueued {
+    pub owner: Pubkey,
+    pub queue_index: u64,
+    pub shares: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalProcessed {
+    pub owner: Pubkey,
+    pub queue_index: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CapitalDeployed {
+    pub amount: u64,
+    pub deployed_capital: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CapitalReturned {
+    pub amount: u64,
+    pub deployed_capital: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityUpdated {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceInitialized {
+    pub meta_mint: Pubkey,
+    pub params: GovernanceParams,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VotesDeposited {
+    pub voter: Pubkey,
+    pub amount: u64,
+    pub total_deposited: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VotesWithdrawn {
+    pub voter: Pubkey,
+    pub amount: u64,
+    pub total_deposited: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: GovernanceAction,
+    pub voting_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal_id: u64,
+    pub voter: Pubkey,
+    pub weight: u64,
+    pub support: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalQueued {
+    pub id: u64,
+    pub passed: bool,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub id: u64,
+    pub action: GovernanceAction,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GuardiansUpdated {
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timelock: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryProposed {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub new_multisig: Pubkey,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryApproved {
+    pub id: u64,
+    pub guardian: Pubkey,
+    pub approvals: u8,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryVetoed {
+    pub id: u64,
+    pub multisig: Pubkey,
+    pub new_multisig: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryExecuted {
+    pub id: u64,
+    pub old_multisig: Pubkey,
+    pub new_multisig: Pubkey,
+    pub approvals: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DistributionCreated {
+    pub id: u64,
+    pub amount: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimAllocated {
+    pub distribution_id: u64,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DistributionClaimed {
+    pub distribution_id: u64,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DistributionSwept {
+    pub distribution_id: u64,
+    pub reclaimed: u64,
+    pub to_insurance: bool,
+    pub profit_pool: u64,
+    pub insurance_reserve: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SweepDestinationUpdated {
+    pub to_insurance: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PnlReportSubmitted {
+    pub cycle_id: u64,
+    pub realized_pnl: i64,
+    pub trade_count: u32,
+    pub trades_hash: [u8; 32],
+    pub amount: u64,
+    pub challenge_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PnlReportFinalized {
+    pub cycle_id: u64,
+    pub amount: u64,
+    pub new_profit_pool: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PnlReportChallenged {
+    pub cycle_id: u64,
+    pub challenger: Pubkey,
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub cycle_id: u64,
+    pub challenger: Pubkey,
+    pub report_valid: bool,
+    pub bond: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ChallengeParamsUpdated {
+    pub challenge_period: i64,
+    pub challenge_bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EngineAttestorUpdated {
+    pub old_attestor: Pubkey,
+    pub new_attestor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RevenueDeposited {
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub total_revenue: u64,
+    pub new_profit_pool: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,32 @@
+#!/usr/bin/env bash
+# Dump the mainnet Metaplex programs and royalty rule set that `anchor test` loads
+# into the local validator (see the [[test.genesis]] entries in Anchor.toml).
+# These fixtures are gitignored; existing ones are kept unless --force is given.
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+mkdir -p tests/fixtures
+
+FORCE=false
+if [[ "${1:-}" == "--force" ]]; then
+  FORCE=true
+fi
+
+TOKEN_METADATA_PROGRAM=metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s
+TOKEN_AUTH_RULES_PROGRAM=auth9SigNpDKz4sJJ1DfCTuZrZNSAgh9sFD3rboVmgg
+FOUNDATION_RULE_SET=eBJLFYPxJmMGKuFwpDWkzxZeUrad92kZRC5BJLpzyT9
+
+needs_dump() {
+  [[ "$FORCE" == true || ! -s "$1" ]]
+}
+
+if needs_dump tests/fixtures/mpl_token_metadata.so; then
+  solana program dump -u m "$TOKEN_METADATA_PROGRAM" tests/fixtures/mpl_token_metadata.so
+fi
+if needs_dump tests/fixtures/mpl_token_auth_rules.so; then
+  solana program dump -u m "$TOKEN_AUTH_RULES_PROGRAM" tests/fixtures/mpl_token_auth_rules.so
+fi
+if needs_dump tests/fixtures/metaplex_foundation_rule_set.json; then
+  solana account -u m "$FOUNDATION_RULE_SET" --output json \
+    --output-file tests/fixtures/metaplex_foundation_rule_set.json
+fi
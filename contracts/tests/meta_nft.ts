@@ -1,63 +1,480 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { MetaNft } from "../target/types/meta_nft";
+import { MetaTreasury } from "../target/types/meta_treasury";
 import { expect } from "chai";
+import { createHash } from "crypto";
 import {
+  Ed25519Program,
   Keypair,
   LAMPORTS_PER_SOL,
   PublicKey,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Transaction,
+  TransactionInstruction,
 } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccount,
+  createMint,
+  getAccount,
   getAssociatedTokenAddress,
+  getMint,
+  getTokenMetadata,
+  mintTo,
+  transfer,
 } from "@solana/spl-token";
 
 const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
   "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
 );
+const TOKEN_AUTH_RULES_PROGRAM_ID = new PublicKey(
+  "auth9SigNpDKz4sJJ1DfCTuZrZNSAgh9sFD3rboVmgg"
+);
+// Metaplex Foundation royalty rule set, loaded from tests/fixtures
+const FOUNDATION_RULE_SET = new PublicKey("eBJLFYPxJmMGKuFwpDWkzxZeUrad92kZRC5BJLpzyT9");
+// Strategy NFT account in the pre-extension layout, loaded from
+// tests/fixtures/legacy_strategy_nft.json. Its mint is derived from this seed and its
+// genes hash is the hex of sha256("legacyhash").
+const LEGACY_STRATEGY_ID = "legacy-strategy-001";
+const LEGACY_MINT_SEED = new Uint8Array(32).fill(38);
+
+interface RoyaltyOverride {
+  sellerFeeBasisPoints: number;
+  creators: { address: PublicKey; share: number }[];
+}
+
+interface MintAttestation {
+  strategyId: string;
+  genesHash: number[];
+  archetype: string;
+  generation: number;
+  fitnessScore: anchor.BN;
+  totalPnl: anchor.BN;
+  winRate: anchor.BN;
+  tradesExecuted: number;
+  sharpeRatioMilli: number;
+  maxDrawdownBps: number;
+  avgHoldTimeSecs: number;
+  parentA: PublicKey | null;
+  parentB: PublicKey | null;
+  royaltyOverride: RoyaltyOverride | null;
+  owner: PublicKey;
+  expiresAt: anchor.BN;
+}
+
+const borshString = (value: string): Buffer => {
+  const bytes = Buffer.from(value);
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(bytes.length);
+  return Buffer.concat([len, bytes]);
+};
+
+const borshOptionPubkey = (value: PublicKey | null): Buffer =>
+  value ? Buffer.concat([Buffer.from([1]), value.toBuffer()]) : Buffer.from([0]);
+
+const borshOptionRoyalty = (value: RoyaltyOverride | null): Buffer => {
+  if (!value) {
+    return Buffer.from([0]);
+  }
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(value.creators.length);
+  return Buffer.concat([
+    Buffer.from([1]),
+    new anchor.BN(value.sellerFeeBasisPoints).toArrayLike(Buffer, "le", 2),
+    len,
+    ...value.creators.map((c) => Buffer.concat([c.address.toBuffer(), Buffer.from([c.share])])),
+  ]);
+};
+
+// Borsh encoding of the program's `MintAttestation`, signed by the engine attestor
+const encodeMintAttestation = (a: MintAttestation): Buffer =>
+  Buffer.concat([
+    borshString(a.strategyId),
+    Buffer.from(a.genesHash),
+    borshString(a.archetype),
+    new anchor.BN(a.generation).toArrayLike(Buffer, "le", 4),
+    a.fitnessScore.toArrayLike(Buffer, "le", 8),
+    a.totalPnl.toTwos(64).toArrayLike(Buffer, "le", 8),
+    a.winRate.toArrayLike(Buffer, "le", 8),
+    new anchor.BN(a.tradesExecuted).toArrayLike(Buffer, "le", 4),
+    new anchor.BN(a.sharpeRatioMilli).toTwos(32).toArrayLike(Buffer, "le", 4),
+    new anchor.BN(a.maxDrawdownBps).toArrayLike(Buffer, "le", 2),
+    new anchor.BN(a.avgHoldTimeSecs).toArrayLike(Buffer, "le", 4),
+    borshOptionPubkey(a.parentA),
+    borshOptionPubkey(a.parentB),
+    borshOptionRoyalty(a.royaltyOverride),
+    a.owner.toBuffer(),
+    a.expiresAt.toTwos(64).toArrayLike(Buffer, "le", 8),
+  ]);
+
+// Domain-separated bytes an attestor signs, matching `meta_attestation::attestation_message`
+const attestationMessage = (programId: PublicKey, kind: string, payload: Buffer): Buffer =>
+  Buffer.concat([
+    Buffer.from("meta-attestation:v1:"),
+    programId.toBuffer(),
+    borshString(kind),
+    payload,
+  ]);
+
+// Genes of a freshly bred aggressive strategy, in the program's compact encoding
+const sampleGenes = {
+  entryMcapMin: new anchor.BN(20_000),
+  entryMcapMax: new anchor.BN(400_000),
+  entryVolumeMin: new anchor.BN(5_000),
+  twitterFollowersMin: 1_000,
+  telegramMembersMin: 500,
+  holdersMin: 100,
+  buyPatterns: 0b101, // cat_meme, ai_narrative
+  tokenKeywords: 0b11, // ai, agent
+  takeProfitBps: 20_000,
+  stopLossBps: 9_000,
+  timeBasedExitMins: 60,
+  volumeDropExitBps: 4_000,
+  sellSignalFlags: 0b111,
+  mcapCeiling: new anchor.BN(1_000_000),
+  profitSecuringBps: 3_000,
+  trailingStopBps: 1_000,
+  sellPatterns: 0b1111,
+  investmentBps: 1_000,
+  maxSimultaneousPositions: 3,
+  maxDrawdownBps: 2_500,
+  diversificationBps: 5_000,
+};
+
+const sha256 = (data: string | Buffer): number[] => [
+  ...createHash("sha256").update(data).digest(),
+];
+
+const findTokenRecordPda = (mint: PublicKey, tokenAccount: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+      Buffer.from("token_record"),
+      tokenAccount.toBuffer(),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+
+const findGenesRegistryPda = (programId: PublicKey, genesHash: number[]): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("genes_registry"), Buffer.from(genesHash)],
+    programId
+  )[0];
+
+const findEditionSupplyPda = (programId: PublicKey, strategyId: string): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("edition_supply"), Buffer.from(strategyId)],
+    programId
+  )[0];
+
+const findEditionMarkPda = (mint: PublicKey, edition: number): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+      Buffer.from("edition"),
+      Buffer.from(Math.floor(edition / 248).toString()),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+
+const findGenesPda = (programId: PublicKey, mint: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync([Buffer.from("genes"), mint.toBuffer()], programId)[0];
+
+const findBredChildPda = (programId: PublicKey, mint: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync([Buffer.from("bred_child"), mint.toBuffer()], programId)[0];
+
+const findHistoryPda = (programId: PublicKey, mint: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync([Buffer.from("history"), mint.toBuffer()], programId)[0];
+
+const findMetadataPda = (mint: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("metadata"), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+
+const findMasterEditionPda = (mint: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+      Buffer.from("edition"),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+
+const borshString = (value: string): Buffer => {
+  const bytes = Buffer.from(value);
+  const len = Buffer.alloc(4);
+  len.writeUInt32LE(bytes.length);
+  return Buffer.concat([len, bytes]);
+};
+
+// Metaplex CreateMetadataAccountV3 and CreateMasterEditionV3 for an NFT without a
+// collection, the shape strategy NFTs had before collections were verified
+const createLegacyMetadataInstructions = (
+  mint: PublicKey,
+  authority: PublicKey
+): TransactionInstruction[] => {
+  const metadata = findMetadataPda(mint);
+  const masterEdition = findMasterEditionPda(mint);
+  const sellerFee = Buffer.alloc(2);
+  sellerFee.writeUInt16LE(500);
+
+  return [
+    new TransactionInstruction({
+      programId: TOKEN_METADATA_PROGRAM_ID,
+      keys: [
+        { pubkey: metadata, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: false },
+        { pubkey: anchor.web3.SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      data: Buffer.concat([
+        Buffer.from([33]),
+        borshString("Legacy Strategy"),
+        borshString("META"),
+        borshString("https://example.com/legacy.json"),
+        sellerFee,
+        // No creators, collection or uses; mutable; no collection details
+        Buffer.from([0, 0, 0, 1, 0]),
+      ]),
+    }),
+    new TransactionInstruction({
+      programId: TOKEN_METADATA_PROGRAM_ID,
+      keys: [
+        { pubkey: masterEdition, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: true },
+        { pubkey: authority, isSigner: true, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: false },
+        { pubkey: authority, isSigner: true, isWritable: true },
+        { pubkey: metadata, isSigner: false, isWritable: true },
+        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: anchor.web3.SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      // Max supply Some(0)
+      data: Buffer.from([17, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+    }),
+  ];
+};
 
 describe("meta_nft", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
 
   const program = anchor.workspace.MetaNft as Program<MetaNft>;
+  const treasuryProgram = anchor.workspace.MetaTreasury as Program<MetaTreasury>;
   const authority = provider.wallet;
-  const treasury = Keypair.generate();
+  const engine = Keypair.generate();
+  const mintAttestationMessage = (a: MintAttestation): Buffer =>
+    attestationMessage(program.programId, "MintAttestation", encodeMintAttestation(a));
+  const giftRecipient = Keypair.generate();
+
+  // Initialized by the meta_treasury suite, which runs first
+  const [treasuryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury")],
+    treasuryProgram.programId
+  );
 
   let collectionConfigPda: PublicKey;
   let collectionConfigBump: number;
+  let collectionMintPda: PublicKey;
+
+  // Mints `attestation` through mintStrategyNft with the engine's signature. Tests
+  // override the signed message, published genes or individual accounts as needed.
+  const mintStrategy = async (
+    attestation: MintAttestation,
+    overrides: {
+      mint?: Keypair;
+      genes?: typeof sampleGenes;
+      signed?: MintAttestation;
+      accounts?: Record<string, PublicKey | null>;
+      preInstructions?: TransactionInstruction[];
+    } = {}
+  ): Promise<Keypair> => {
+    const mint = overrides.mint ?? Keypair.generate();
+    const genes = overrides.genes ?? null;
+    const [strategyNftPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from(attestation.strategyId)],
+      program.programId
+    );
+
+    await program.methods
+      .mintStrategyNft(
+        attestation.strategyId,
+        attestation.genesHash,
+        attestation.archetype,
+        attestation.generation,
+        attestation.fitnessScore,
+        attestation.totalPnl,
+        attestation.winRate,
+        attestation.tradesExecuted,
+        attestation.sharpeRatioMilli,
+        attestation.maxDrawdownBps,
+        attestation.avgHoldTimeSecs,
+        attestation.parentA,
+        attestation.parentB,
+        attestation.royaltyOverride,
+        attestation.expiresAt,
+        genes
+      )
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        strategyNft: strategyNftPda,
+        genesRegistry: findGenesRegistryPda(program.programId, attestation.genesHash),
+        performanceHistory: findHistoryPda(program.programId, mint.publicKey),
+        strategyGenes: genes ? findGenesPda(program.programId, mint.publicKey) : null,
+        editionSupply: findEditionSupplyPda(program.programId, attestation.strategyId),
+        parentANft: null,
+        parentBNft: null,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, attestation.owner),
+        metadata: findMetadataPda(mint.publicKey),
+        masterEdition: findMasterEditionPda(mint.publicKey),
+        collectionMint: collectionMintPda,
+        collectionMetadata: findMetadataPda(collectionMintPda),
+        collectionMasterEdition: findMasterEditionPda(collectionMintPda),
+        tokenRecord: null,
+        authorizationRules: null,
+        authorizationRulesProgram: null,
+        treasury: treasuryPda,
+        treasuryProgram: treasuryProgram.programId,
+        payer: authority.publicKey,
+        recipient: attestation.owner,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        ...overrides.accounts,
+      })
+      .preInstructions([
+        ...(overrides.preInstructions ?? []),
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: engine.secretKey,
+          message: mintAttestationMessage(overrides.signed ?? attestation),
+        }),
+      ])
+      .signers([mint])
+      .rpc();
+
+    return mint;
+  };
 
   before(async () => {
     [collectionConfigPda, collectionConfigBump] = PublicKey.findProgramAddressSync(
       [Buffer.from("collection")],
       program.programId
     );
-
-    // Airdrop to treasury
-    const sig = await provider.connection.requestAirdrop(
-      treasury.publicKey,
-      0.1 * LAMPORTS_PER_SOL
+    [collectionMintPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("collection_mint")],
+      program.programId
     );
+
+    // The engine pays for bred_child records and growing Token-2022 metadata
+    const sig = await provider.connection.requestAirdrop(engine.publicKey, LAMPORTS_PER_SOL);
     await provider.connection.confirmTransaction(sig);
   });
 
   it("initializes the collection", async () => {
     await program.methods
-      .initializeCollection("$META Strategies", "META", "https://meta.io/collection.json")
+      .initializeCollection(
+        "$META Strategies",
+        "META",
+        "https://meta.io/collection.json",
+        "$META Strategy",
+        "https://meta.io/nft/"
+      )
       .accounts({
         collectionConfig: collectionConfigPda,
+        collectionMint: collectionMintPda,
+        collectionTokenAccount: await getAssociatedTokenAddress(
+          collectionMintPda,
+          collectionConfigPda,
+          true
+        ),
+        collectionMetadata: findMetadataPda(collectionMintPda),
+        collectionMasterEdition: findMasterEditionPda(collectionMintPda),
         authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       })
       .rpc();
 
     const config = await program.account.collectionConfig.fetch(collectionConfigPda);
 
     expect(config.authority.toString()).to.equal(authority.publicKey.toString());
+    expect(config.collectionMint.toString()).to.equal(collectionMintPda.toString());
     expect(config.totalMinted.toNumber()).to.equal(0);
     expect(config.mintPriceLamports.toNumber()).to.equal(100_000_000);
     expect(config.isActive).to.be.true;
+    expect(config.symbol).to.equal("META");
+    expect(config.sellerFeeBasisPoints).to.equal(500);
+    expect(config.creators[0].address.toString()).to.equal(collectionConfigPda.toString());
+  });
+
+  it("updates the royalty config", async () => {
+    const discoverer = Keypair.generate();
+
+    await program.methods
+      .updateRoyaltyConfig(750, [
+        { address: collectionConfigPda, share: 80 },
+        { address: discoverer.publicKey, share: 20 },
+      ])
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const config = await program.account.collectionConfig.fetch(collectionConfigPda);
+    expect(config.sellerFeeBasisPoints).to.equal(750);
+    expect(config.creators.length).to.equal(2);
+  });
+
+  it("rejects creator shares that don't add up to 100", async () => {
+    try {
+      await program.methods
+        .updateRoyaltyConfig(500, [{ address: collectionConfigPda, share: 90 }])
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("InvalidRoyaltyConfig");
+    }
+  });
+
+  it("rejects royalties above the cap", async () => {
+    try {
+      await program.methods
+        .updateRoyaltyConfig(1500, [{ address: collectionConfigPda, share: 100 }])
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("InvalidRoyaltyConfig");
+    }
   });
 
   it("updates mint price", async () => {
@@ -84,6 +501,19 @@ describe("meta_nft", () => {
       .rpc();
   });
 
+  it("sets the mint attestor", async () => {
+    await program.methods
+      .setAttestor(engine.publicKey)
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const config = await program.account.collectionConfig.fetch(collectionConfigPda);
+    expect(config.attestor.toString()).to.equal(engine.publicKey.toString());
+  });
+
   it("mints a strategy NFT", async () => {
     const strategyId = "test-strategy-001";
     const mint = Keypair.generate();
@@ -93,183 +523,1582 @@ describe("meta_nft", () => {
       program.programId
     );
 
-    const tokenAccount = await getAssociatedTokenAddress(
-      mint.publicKey,
-      authority.publicKey
-    );
-
-    const [metadataPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("metadata"),
-        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
-        mint.publicKey.toBuffer(),
-      ],
-      TOKEN_METADATA_PROGRAM_ID
-    );
-
-    const [masterEditionPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("metadata"),
-        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
-        mint.publicKey.toBuffer(),
-        Buffer.from("edition"),
-      ],
-      TOKEN_METADATA_PROGRAM_ID
-    );
+    const treasuryBalanceBefore = await provider.connection.getBalance(treasuryPda);
+    const treasuryStateBefore = await treasuryProgram.account.treasuryState.fetch(treasuryPda);
 
-    const treasuryBalanceBefore = await provider.connection.getBalance(treasury.publicKey);
+    const attestation: MintAttestation = {
+      strategyId,
+      genesHash: sha256("abc123hash"),
+      archetype: "aggressive",
+      generation: 5,
+      fitnessScore: new anchor.BN(8500), // fitness score (85.00)
+      totalPnl: new anchor.BN(1500000), // total PnL in lamports
+      winRate: new anchor.BN(6800), // win rate (68.00%)
+      tradesExecuted: 150,
+      sharpeRatioMilli: 1850, // sharpe ratio (1.85)
+      maxDrawdownBps: 1200, // max drawdown (12.00%)
+      avgHoldTimeSecs: 5400,
+      parentA: null,
+      parentB: null,
+      royaltyOverride: null,
+      owner: authority.publicKey,
+      expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 300),
+    };
 
-    await program.methods
-      .mintStrategyNft(
-        strategyId,
-        "abc123hash",
-        "Alpha Hunter #1",
-        "META",
-        "https://meta.io/nft/1.json",
-        "aggressive",
-        5, // generation
-        new anchor.BN(8500), // fitness score (85.00)
-        new anchor.BN(1500000), // total PnL in lamports
-        new anchor.BN(6800), // win rate (68.00%)
-        150 // trades executed
-      )
-      .accounts({
-        collectionConfig: collectionConfigPda,
-        strategyNft: strategyNftPda,
-        mint: mint.publicKey,
-        tokenAccount,
-        metadata: metadataPda,
-        masterEdition: masterEditionPda,
-        treasury: treasury.publicKey,
-        payer: authority.publicKey,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
-        systemProgram: anchor.web3.SystemProgram.programId,
-        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-      })
-      .signers([mint])
-      .rpc();
+    await mintStrategy(attestation, { mint });
 
     // Verify strategy NFT data
     const strategyNft = await program.account.strategyNftData.fetch(strategyNftPda);
     expect(strategyNft.strategyId).to.equal(strategyId);
-    expect(strategyNft.genesHash).to.equal("abc123hash");
+    expect(strategyNft.genesHash).to.deep.equal(sha256("abc123hash"));
     expect(strategyNft.archetype).to.equal("aggressive");
     expect(strategyNft.generation).to.equal(5);
     expect(strategyNft.fitnessScore.toNumber()).to.equal(8500);
+    expect(strategyNft.winRate.toNumber()).to.equal(6800);
+    expect(strategyNft.sharpeRatioMilli).to.equal(1850);
+    expect(strategyNft.maxDrawdownBps).to.equal(1200);
+    expect(strategyNft.avgHoldTimeSecs).to.equal(5400);
     expect(strategyNft.owner.toString()).to.equal(authority.publicKey.toString());
+    expect(strategyNft.status).to.deep.equal({ active: {} });
 
     // Verify collection config updated
     const config = await program.account.collectionConfig.fetch(collectionConfigPda);
     expect(config.totalMinted.toNumber()).to.equal(1);
 
-    // Verify treasury received payment
-    const treasuryBalanceAfter = await provider.connection.getBalance(treasury.publicKey);
+    // Verify treasury received payment and booked it as revenue
+    const treasuryBalanceAfter = await provider.connection.getBalance(treasuryPda);
+    const treasuryStateAfter = await treasuryProgram.account.treasuryState.fetch(treasuryPda);
     expect(treasuryBalanceAfter - treasuryBalanceBefore).to.equal(10_000_000);
+    expect(
+      treasuryStateAfter.totalRevenue.toNumber() - treasuryStateBefore.totalRevenue.toNumber()
+    ).to.equal(10_000_000);
   });
 
-  it("toggles minting", async () => {
-    // Pause minting
+  it("updates strategy stats and refreshes the metadata URI", async () => {
+    const [strategyNftPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from("test-strategy-001")],
+      program.programId
+    );
+    const before = await program.account.strategyNftData.fetch(strategyNftPda);
+
     await program.methods
-      .toggleMinting(false)
+      .updateStrategyStats(
+        new anchor.BN(9100),
+        new anchor.BN(2500000),
+        new anchor.BN(7100),
+        210,
+        2100,
+        1100,
+        4800,
+        "https://meta.io/nft/test-strategy-001-v2.json"
+      )
       .accounts({
         collectionConfig: collectionConfigPda,
-        authority: authority.publicKey,
+        strategyNft: strategyNftPda,
+        performanceHistory: findHistoryPda(program.programId, before.mint),
+        metadata: findMetadataPda(before.mint),
+        mint: null,
+        attestor: engine.publicKey,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
+      .signers([engine])
       .rpc();
 
-    let config = await program.account.collectionConfig.fetch(collectionConfigPda);
-    expect(config.isActive).to.be.false;
+    const after = await program.account.strategyNftData.fetch(strategyNftPda);
+    expect(after.fitnessScore.toNumber()).to.equal(9100);
+    expect(after.tradesExecuted).to.equal(210);
+    expect(after.sharpeRatioMilli).to.equal(2100);
+    expect(after.lastUpdated.toNumber()).to.be.at.least(before.lastUpdated.toNumber());
 
-    // Resume minting
-    await program.methods
-      .toggleMinting(true)
-      .accounts({
-        collectionConfig: collectionConfigPda,
-        authority: authority.publicKey,
-      })
-      .rpc();
+    const history = await program.account.performanceHistory.fetch(
+      findHistoryPda(program.programId, before.mint)
+    );
+    expect(history.len).to.equal(2);
+    expect(history.snapshots[1].fitnessScore.toNumber()).to.equal(9100);
+  });
 
-    config = await program.account.collectionConfig.fetch(collectionConfigPda);
-    expect(config.isActive).to.be.true;
+  it("keeps the newest snapshots in order once the history wraps", async () => {
+    const [strategyNftPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from("test-strategy-001")],
+      program.programId
+    );
+    const { mint } = await program.account.strategyNftData.fetch(strategyNftPda);
+    const historyPda = findHistoryPda(program.programId, mint);
+    const capacity = 32;
+
+    // Overflow the ring by a few entries, tagging each write by its trade count
+    const writes = Array.from({ length: capacity + 3 }, (_, i) => 1000 + i);
+    for (const tradesExecuted of writes) {
+      await program.methods
+        .updateStrategyStats(
+          new anchor.BN(9100),
+          new anchor.BN(2500000),
+          new anchor.BN(7100),
+          tradesExecuted,
+          2100,
+          1100,
+          4800,
+          null
+        )
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          strategyNft: strategyNftPda,
+          performanceHistory: historyPda,
+          metadata: null,
+          mint: null,
+          attestor: engine.publicKey,
+          metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          tokenProgram: null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([engine])
+        .rpc();
+    }
+
+    const history = await program.account.performanceHistory.fetch(historyPda);
+    expect(history.len).to.equal(capacity);
+
+    // `head` is the next slot to overwrite, so it holds the oldest surviving entry
+    const oldestFirst = Array.from(
+      { length: capacity },
+      (_, i) => history.snapshots[(history.head + i) % capacity].tradesExecuted
+    );
+    expect(oldestFirst).to.deep.equal(writes.slice(writes.length - capacity));
   });
 
-  it("fails to mint when paused", async () => {
-    // Pause minting first
-    await program.methods
-      .toggleMinting(false)
-      .accounts({
-        collectionConfig: collectionConfigPda,
-        authority: authority.publicKey,
-      })
-      .rpc();
+  it("overrides a strategy's royalties and reverts to the collection's", async () => {
+    const [strategyNftPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from("test-strategy-001")],
+      program.programId
+    );
+    const { mint } = await program.account.strategyNftData.fetch(strategyNftPda);
+    const discoverer = Keypair.generate();
+    const updateRoyalties = (royaltyOverride: RoyaltyOverride | null) =>
+      program.methods
+        .updateNftRoyalties(royaltyOverride)
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          strategyNft: strategyNftPda,
+          metadata: findMetadataPda(mint),
+          attestor: engine.publicKey,
+          metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        })
+        .signers([engine])
+        .rpc();
 
-    const strategyId = "test-strategy-002";
+    await updateRoyalties({
+      sellerFeeBasisPoints: 600,
+      creators: [
+        { address: collectionConfigPda, share: 70 },
+        { address: discoverer.publicKey, share: 30 },
+      ],
+    });
+
+    let strategyNft = await program.account.strategyNftData.fetch(strategyNftPda);
+    expect(strategyNft.royaltyOverride.sellerFeeBasisPoints).to.equal(600);
+    expect(strategyNft.royaltyOverride.creators[1].address.toString()).to.equal(
+      discoverer.publicKey.toString()
+    );
+
+    await updateRoyalties(null);
+
+    strategyNft = await program.account.strategyNftData.fetch(strategyNftPda);
+    expect(strategyNft.royaltyOverride).to.be.null;
+  });
+
+  it("rejects a second mint of already registered genes", async () => {
+    const strategyId = "test-strategy-001-copy";
+    const mint = Keypair.generate();
+
+    // Same genes as test-strategy-001 under a new strategy id
+    const attestation: MintAttestation = {
+      strategyId,
+      genesHash: sha256("abc123hash"),
+      archetype: "aggressive",
+      generation: 6,
+      fitnessScore: new anchor.BN(8600),
+      totalPnl: new anchor.BN(1600000),
+      winRate: new anchor.BN(6900),
+      tradesExecuted: 160,
+      sharpeRatioMilli: 1900,
+      maxDrawdownBps: 1100,
+      avgHoldTimeSecs: 5000,
+      parentA: null,
+      parentB: null,
+      royaltyOverride: null,
+      owner: authority.publicKey,
+      expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 300),
+    };
+
+    const registryPda = findGenesRegistryPda(program.programId, attestation.genesHash);
+    const registry = await program.account.genesRegistry.fetch(registryPda);
+    expect(registry.mint.toString()).to.not.equal(mint.publicKey.toString());
+
+    try {
+      await mintStrategy(attestation, { mint });
+
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("already in use");
+    }
+  });
+
+  it("rejects a mint whose stats differ from the attestation", async () => {
+    const strategyId = "test-strategy-forged";
+    const mint = Keypair.generate();
+
+    const attestation: MintAttestation = {
+      strategyId,
+      genesHash: sha256("forgedhash"),
+      archetype: "sniper",
+      generation: 1,
+      fitnessScore: new anchor.BN(1000),
+      totalPnl: new anchor.BN(0),
+      winRate: new anchor.BN(1000),
+      tradesExecuted: 3,
+      sharpeRatioMilli: -250,
+      maxDrawdownBps: 4000,
+      avgHoldTimeSecs: 600,
+      parentA: null,
+      parentB: null,
+      royaltyOverride: null,
+      owner: authority.publicKey,
+      expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 300),
+    };
+
+    try {
+      await mintStrategy(
+        { ...attestation, fitnessScore: new anchor.BN(9999) }, // inflated fitness
+        { mint, signed: attestation }
+      );
+
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("InvalidAttestation");
+    }
+  });
+
+  it("mints with published genes that hash to the genes hash", async () => {
+    const strategyId = "test-strategy-genes";
+    const mint = Keypair.generate();
+
+    const genesPda = findGenesPda(program.programId, mint.publicKey);
+
+    const attestation: MintAttestation = {
+      strategyId,
+      genesHash: sha256(program.coder.types.encode("StrategyGenes", sampleGenes)),
+      archetype: "aggressive",
+      generation: 2,
+      fitnessScore: new anchor.BN(6000),
+      totalPnl: new anchor.BN(250000),
+      winRate: new anchor.BN(5500),
+      tradesExecuted: 40,
+      sharpeRatioMilli: 900,
+      maxDrawdownBps: 1800,
+      avgHoldTimeSecs: 2400,
+      parentA: null,
+      parentB: null,
+      royaltyOverride: null,
+      owner: authority.publicKey,
+      expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 300),
+    };
+
+    await mintStrategy(attestation, { mint, genes: sampleGenes });
+
+    const record = await program.account.strategyGenesRecord.fetch(genesPda);
+    expect(record.mint.toString()).to.equal(mint.publicKey.toString());
+    expect(record.genes.takeProfitBps).to.equal(20_000);
+    expect(record.genes.tokenKeywords).to.equal(0b11);
+  });
+
+  it("rejects genes that don't hash to the genes hash", async () => {
+    const strategyId = "test-strategy-bad-genes";
+    const mint = Keypair.generate();
+
+    const attestation: MintAttestation = {
+      strategyId,
+      genesHash: sha256(
+        program.coder.types.encode("StrategyGenes", { ...sampleGenes, holdersMin: 250 })
+      ),
+      archetype: "aggressive",
+      generation: 2,
+      fitnessScore: new anchor.BN(6000),
+      totalPnl: new anchor.BN(250000),
+      winRate: new anchor.BN(5500),
+      tradesExecuted: 40,
+      sharpeRatioMilli: 900,
+      maxDrawdownBps: 1800,
+      avgHoldTimeSecs: 2400,
+      parentA: null,
+      parentB: null,
+      royaltyOverride: null,
+      owner: authority.publicKey,
+      expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 300),
+    };
+
+    try {
+      // Not the genes that were attested
+      await mintStrategy(attestation, { mint, genes: { ...sampleGenes, stopLossBps: 5_000 } });
+
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("GenesHashMismatch");
+    }
+  });
+
+  it("mints to a recipient other than the payer", async () => {
+    const strategyId = "test-strategy-gift";
     const mint = Keypair.generate();
+    const recipient = giftRecipient;
 
     const [strategyNftPda] = PublicKey.findProgramAddressSync(
       [Buffer.from("strategy_nft"), Buffer.from(strategyId)],
       program.programId
     );
+    const recipientTokens = await getAssociatedTokenAddress(mint.publicKey, recipient.publicKey);
 
-    const tokenAccount = await getAssociatedTokenAddress(
-      mint.publicKey,
-      authority.publicKey
-    );
+    const attestation: MintAttestation = {
+      strategyId,
+      genesHash: sha256("gifthash"),
+      archetype: "balanced",
+      generation: 4,
+      fitnessScore: new anchor.BN(7200),
+      totalPnl: new anchor.BN(800000),
+      winRate: new anchor.BN(6100),
+      tradesExecuted: 90,
+      sharpeRatioMilli: 1400,
+      maxDrawdownBps: 1500,
+      avgHoldTimeSecs: 3000,
+      parentA: null,
+      parentB: null,
+      royaltyOverride: null,
+      owner: recipient.publicKey,
+      expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 300),
+    };
 
-    const [metadataPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("metadata"),
-        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
-        mint.publicKey.toBuffer(),
-      ],
-      TOKEN_METADATA_PROGRAM_ID
+    // The authority sponsors rent and the fee; the recipient never signs
+    await mintStrategy(attestation, { mint });
+
+    const strategyNft = await program.account.strategyNftData.fetch(strategyNftPda);
+    expect(strategyNft.owner.toString()).to.equal(recipient.publicKey.toString());
+    const tokens = await getAccount(provider.connection, recipientTokens);
+    expect(Number(tokens.amount)).to.equal(1);
+  });
+
+  it("syncs the owner after the NFT is transferred", async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    const buyer = Keypair.generate();
+
+    const [strategyNftPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from("test-strategy-genes")],
+      program.programId
     );
+    const { mint } = await program.account.strategyNftData.fetch(strategyNftPda);
 
-    const [masterEditionPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("metadata"),
-        TOKEN_METADATA_PROGRAM_ID.toBuffer(),
-        mint.publicKey.toBuffer(),
-        Buffer.from("edition"),
-      ],
-      TOKEN_METADATA_PROGRAM_ID
+    const buyerTokens = await createAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      mint,
+      buyer.publicKey
+    );
+    await transfer(
+      provider.connection,
+      payer,
+      await getAssociatedTokenAddress(mint, authority.publicKey),
+      buyerTokens,
+      payer,
+      1
     );
 
+    // Anyone can sync; the old owner's emptied account is rejected
     try {
       await program.methods
-        .mintStrategyNft(
-          strategyId,
-          "hash456",
-          "Failed Mint",
-          "META",
-          "https://meta.io/nft/2.json",
-          "conservative",
-          3,
-          new anchor.BN(7000),
-          new anchor.BN(500000),
-          new anchor.BN(5500),
-          75
-        )
+        .syncOwner()
         .accounts({
-          collectionConfig: collectionConfigPda,
           strategyNft: strategyNftPda,
-          mint: mint.publicKey,
-          tokenAccount,
-          metadata: metadataPda,
-          masterEdition: masterEditionPda,
-          treasury: treasury.publicKey,
-          payer: authority.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-          metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          tokenAccount: await getAssociatedTokenAddress(mint, authority.publicKey),
+        })
+        .rpc();
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("NotNftHolder");
+    }
+
+    await program.methods
+      .syncOwner()
+      .accounts({
+        strategyNft: strategyNftPda,
+        tokenAccount: buyerTokens,
+      })
+      .rpc();
+
+    const strategyNft = await program.account.strategyNftData.fetch(strategyNftPda);
+    expect(strategyNft.owner.toString()).to.equal(buyer.publicKey.toString());
+  });
+
+  it("records a strategy's death with fossil artwork", async () => {
+    const [strategyNftPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from("test-strategy-genes")],
+      program.programId
+    );
+    const { mint } = await program.account.strategyNftData.fetch(strategyNftPda);
+
+    const recordDeath = () =>
+      program.methods
+        .recordStrategyDeath("https://meta.io/nft/fossils/test-strategy-genes.json")
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          strategyNft: strategyNftPda,
+          metadata: findMetadataPda(mint),
+          mint: null,
+          attestor: engine.publicKey,
+          metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          tokenProgram: null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([engine])
+        .rpc();
+
+    await recordDeath();
+
+    const strategyNft = await program.account.strategyNftData.fetch(strategyNftPda);
+    expect(strategyNft.status).to.deep.equal({ dead: {} });
+    expect(strategyNft.diedAt.toNumber()).to.be.greaterThan(0);
+
+    try {
+      await recordDeath();
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("StrategyDead");
+    }
+  });
+
+  it("mints a child strategy linked to its parents", async () => {
+    const strategyId = "test-strategy-child";
+    const mint = Keypair.generate();
+
+    const [strategyNftPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from(strategyId)],
+      program.programId
+    );
+    const [parentAPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from("test-strategy-001")],
+      program.programId
+    );
+    const [parentBPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from("test-strategy-genes")],
+      program.programId
+    );
+    const parentA = await program.account.strategyNftData.fetch(parentAPda);
+    const parentB = await program.account.strategyNftData.fetch(parentBPda);
+
+    const attestation: MintAttestation = {
+      strategyId,
+      genesHash: sha256("childhash"),
+      archetype: "aggressive",
+      generation: 6,
+      fitnessScore: new anchor.BN(8800),
+      totalPnl: new anchor.BN(0),
+      winRate: new anchor.BN(0),
+      tradesExecuted: 0,
+      sharpeRatioMilli: 0,
+      maxDrawdownBps: 0,
+      avgHoldTimeSecs: 0,
+      parentA: parentA.mint,
+      parentB: parentB.mint,
+      royaltyOverride: null,
+      owner: authority.publicKey,
+      expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 300),
+    };
+
+    await mintStrategy(attestation, {
+      mint,
+      accounts: { parentANft: parentAPda, parentBNft: parentBPda },
+    });
+
+    const child = await program.account.strategyNftData.fetch(strategyNftPda);
+    expect(child.parentA.toString()).to.equal(parentA.mint.toString());
+    expect(child.parentB.toString()).to.equal(parentB.mint.toString());
+  });
+
+  describe("breeding", () => {
+    const strategyPda = (strategyId: string): PublicKey =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("strategy_nft"), Buffer.from(strategyId)],
+        program.programId
+      )[0];
+    const parentAPda = strategyPda("test-strategy-001");
+    const parentBPda = strategyPda("test-strategy-child");
+
+    const requestBreeding = async (): Promise<[PublicKey, PublicKey]> => {
+      const config = await program.account.collectionConfig.fetch(collectionConfigPda);
+      const [requestPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("breeding"), config.totalBreedingRequests.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const [escrowPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("breeding_escrow"), requestPda.toBuffer()],
+        program.programId
+      );
+      const parentA = await program.account.strategyNftData.fetch(parentAPda);
+      const parentB = await program.account.strategyNftData.fetch(parentBPda);
+
+      await program.methods
+        .requestBreeding()
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          breedingRequest: requestPda,
+          escrow: escrowPda,
+          parentANft: parentAPda,
+          parentBNft: parentBPda,
+          parentAToken: await getAssociatedTokenAddress(parentA.mint, authority.publicKey),
+          parentBToken: await getAssociatedTokenAddress(parentB.mint, authority.publicKey),
+          ownerA: authority.publicKey,
+          ownerB: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      return [requestPda, escrowPda];
+    };
+
+    const fulfilBreeding = async (requestPda: PublicKey, escrowPda: PublicKey, childId: string) => {
+      const request = await program.account.breedingRequest.fetch(requestPda);
+      const child = await program.account.strategyNftData.fetch(strategyPda(childId));
+
+      await program.methods
+        .fulfilBreeding()
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          breedingRequest: requestPda,
+          escrow: escrowPda,
+          childNft: strategyPda(childId),
+          childToken: await getAssociatedTokenAddress(child.mint, request.requester),
+          bredChild: findBredChildPda(program.programId, child.mint),
+          parentANft: parentAPda,
+          parentBNft: parentBPda,
+          treasury: treasuryPda,
+          treasuryProgram: treasuryProgram.programId,
+          attestor: engine.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([engine])
+        .rpc();
+    };
+
+    it("rejects a breeding fee below the escrow's rent-exempt minimum", async () => {
+      try {
+        await program.methods
+          .updateBreedingConfig(new anchor.BN(1_000), new anchor.BN(86_400))
+          .accounts({
+            collectionConfig: collectionConfigPda,
+            authority: authority.publicKey,
+          })
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidBreedingConfig");
+      }
+    });
+
+    it("escrows the fee and refunds it after the timeout", async () => {
+      await program.methods
+        .updateBreedingConfig(new anchor.BN(50_000_000), new anchor.BN(1))
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      const [requestPda, escrowPda] = await requestBreeding();
+
+      expect(await provider.connection.getBalance(escrowPda)).to.equal(50_000_000);
+      const parentA = await program.account.strategyNftData.fetch(parentAPda);
+      expect(parentA.status).to.deep.equal({ breeding: {} });
+
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+
+      await program.methods
+        .refundBreeding()
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          breedingRequest: requestPda,
+          escrow: escrowPda,
+          parentANft: parentAPda,
+          parentBNft: parentBPda,
+          requester: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      const request = await program.account.breedingRequest.fetch(requestPda);
+      expect(request.status).to.deep.equal({ refunded: {} });
+      expect(await provider.connection.getBalance(escrowPda)).to.equal(0);
+      const parentAAfter = await program.account.strategyNftData.fetch(parentAPda);
+      expect(parentAAfter.status).to.deep.equal({ active: {} });
+    });
+
+    it("books the fee as revenue when the engine mints the child", async () => {
+      await program.methods
+        .updateBreedingConfig(new anchor.BN(50_000_000), new anchor.BN(86_400))
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      const [requestPda, escrowPda] = await requestBreeding();
+      const request = await program.account.breedingRequest.fetch(requestPda);
+
+      const strategyId = "test-strategy-bred";
+      const mint = Keypair.generate();
+      const attestation: MintAttestation = {
+        strategyId,
+        genesHash: sha256("bredhash"),
+        archetype: "aggressive",
+        generation: 7,
+        fitnessScore: new anchor.BN(0),
+        totalPnl: new anchor.BN(0),
+        winRate: new anchor.BN(0),
+        tradesExecuted: 0,
+        sharpeRatioMilli: 0,
+        maxDrawdownBps: 0,
+        avgHoldTimeSecs: 0,
+        parentA: request.parentA,
+        parentB: request.parentB,
+        royaltyOverride: null,
+        owner: request.requester,
+        expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 300),
+      };
+
+      await mintStrategy(attestation, {
+        mint,
+        accounts: { parentANft: parentAPda, parentBNft: parentBPda },
+      });
+
+      const treasuryBefore = await treasuryProgram.account.treasuryState.fetch(treasuryPda);
+
+      await fulfilBreeding(requestPda, escrowPda, strategyId);
+
+      const fulfilled = await program.account.breedingRequest.fetch(requestPda);
+      expect(fulfilled.status).to.deep.equal({ fulfilled: {} });
+      expect(fulfilled.childMint.toString()).to.equal(mint.publicKey.toString());
+
+      const treasuryAfter = await treasuryProgram.account.treasuryState.fetch(treasuryPda);
+      expect(
+        treasuryAfter.totalRevenue.toNumber() - treasuryBefore.totalRevenue.toNumber()
+      ).to.equal(50_000_000);
+
+      const bredChild = await program.account.bredChild.fetch(
+        findBredChildPda(program.programId, mint.publicKey)
+      );
+      expect(bredChild.requestId.toString()).to.equal(request.id.toString());
+    });
+
+    it("rejects a child that already fulfilled a request", async () => {
+      await program.methods
+        .updateBreedingConfig(new anchor.BN(50_000_000), new anchor.BN(1))
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+
+      const [requestPda, escrowPda] = await requestBreeding();
+
+      try {
+        await fulfilBreeding(requestPda, escrowPda, "test-strategy-bred");
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.logs.join("\n")).to.include("already in use");
+      }
+
+      // The request stays open and refunds like any unfulfilled one
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+
+      await program.methods
+        .refundBreeding()
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          breedingRequest: requestPda,
+          escrow: escrowPda,
+          parentANft: parentAPda,
+          parentBNft: parentBPda,
+          requester: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      const request = await program.account.breedingRequest.fetch(requestPda);
+      expect(request.status).to.deep.equal({ refunded: {} });
+    });
+  });
+
+  it("prints numbered editions up to the configured supply", async () => {
+    const strategyId = "test-strategy-editions";
+    const mint = Keypair.generate();
+    const [strategyNftPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from(strategyId)],
+      program.programId
+    );
+    const editionSupplyPda = findEditionSupplyPda(program.programId, strategyId);
+
+    await program.methods
+      .setEditionSupply(strategyId, new anchor.BN(2))
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        editionSupply: editionSupplyPda,
+        strategyNft: strategyNftPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const attestation: MintAttestation = {
+      strategyId,
+      genesHash: sha256("editionshash"),
+      archetype: "conservative",
+      generation: 9,
+      fitnessScore: new anchor.BN(9500),
+      totalPnl: new anchor.BN(9000000),
+      winRate: new anchor.BN(7400),
+      tradesExecuted: 400,
+      sharpeRatioMilli: 2600,
+      maxDrawdownBps: 800,
+      avgHoldTimeSecs: 7200,
+      parentA: null,
+      parentB: null,
+      royaltyOverride: null,
+      owner: authority.publicKey,
+      expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 300),
+    };
+
+    await mintStrategy(attestation, { mint });
+
+    // The supply is fixed once the master edition exists
+    try {
+      await program.methods
+        .setEditionSupply(strategyId, new anchor.BN(10))
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          editionSupply: editionSupplyPda,
+          strategyNft: strategyNftPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("EditionSupplyLocked");
+    }
+
+    const [editionEscrowPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("edition_escrow"), mint.publicKey.toBuffer()],
+      program.programId
+    );
+    const holderTokenAccount = await getAssociatedTokenAddress(mint.publicKey, authority.publicKey);
+    const escrowTokenAccount = await getAssociatedTokenAddress(
+      mint.publicKey,
+      editionEscrowPda,
+      true
+    );
+
+    // The holder escrows the master once, after which anyone can print
+    await program.methods
+      .escrowMasterEdition()
+      .accounts({
+        strategyNft: strategyNftPda,
+        editionEscrow: editionEscrowPda,
+        masterMint: mint.publicKey,
+        holderTokenAccount,
+        escrowTokenAccount,
+        holder: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const buyer = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(buyer.publicKey, LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+
+    const printEdition = async (editionMint: Keypair, edition: number) =>
+      program.methods
+        .mintEdition()
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          strategyNft: strategyNftPda,
+          masterMint: mint.publicKey,
+          masterMetadata: findMetadataPda(mint.publicKey),
+          masterEdition: findMasterEditionPda(mint.publicKey),
+          editionEscrow: editionEscrowPda,
+          masterTokenAccount: escrowTokenAccount,
+          editionMint: editionMint.publicKey,
+          editionTokenAccount: await getAssociatedTokenAddress(
+            editionMint.publicKey,
+            buyer.publicKey
+          ),
+          editionMetadata: findMetadataPda(editionMint.publicKey),
+          edition: findMasterEditionPda(editionMint.publicKey),
+          editionMark: findEditionMarkPda(mint.publicKey, edition),
+          treasury: treasuryPda,
+          treasuryProgram: treasuryProgram.programId,
+          buyer: buyer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          metadataProgram: TOKEN_METADATA_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         })
-        .signers([mint])
+        .signers([buyer, editionMint])
         .rpc();
 
+    const treasuryBefore = await treasuryProgram.account.treasuryState.fetch(treasuryPda);
+    const editionMint = Keypair.generate();
+    await printEdition(editionMint, 1);
+    await printEdition(Keypair.generate(), 2);
+
+    const strategyNft = await program.account.strategyNftData.fetch(strategyNftPda);
+    expect(strategyNft.maxEditions.toNumber()).to.equal(2);
+    expect(strategyNft.editionsPrinted.toNumber()).to.equal(2);
+    const editionTokens = await getAccount(
+      provider.connection,
+      await getAssociatedTokenAddress(editionMint.publicKey, buyer.publicKey)
+    );
+    expect(editionTokens.amount.toString()).to.equal("1");
+    const treasuryAfter = await treasuryProgram.account.treasuryState.fetch(treasuryPda);
+    expect(
+      treasuryAfter.totalRevenue.toNumber() - treasuryBefore.totalRevenue.toNumber()
+    ).to.equal(40_000_000);
+
+    try {
+      await printEdition(Keypair.generate(), 3);
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("EditionSupplyExhausted");
+    }
+
+    await program.methods
+      .releaseMasterEdition()
+      .accounts({
+        strategyNft: strategyNftPda,
+        editionEscrow: editionEscrowPda,
+        escrowTokenAccount,
+        depositorTokenAccount: holderTokenAccount,
+        depositor: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    expect(
+      (await getAccount(provider.connection, holderTokenAccount)).amount.toString()
+    ).to.equal("1");
+    expect(await provider.connection.getAccountInfo(editionEscrowPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(escrowTokenAccount)).to.be.null;
+  });
+
+  it("burns a strategy NFT and closes its accounts", async () => {
+    const [strategyNftPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from("test-strategy-gift")],
+      program.programId
+    );
+    const { mint } = await program.account.strategyNftData.fetch(strategyNftPda);
+    const historyPda = findHistoryPda(program.programId, mint);
+    const configBefore = await program.account.collectionConfig.fetch(collectionConfigPda);
+
+    await program.methods
+      .burnStrategyNft()
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        strategyNft: strategyNftPda,
+        performanceHistory: historyPda,
+        mint,
+        tokenAccount: await getAssociatedTokenAddress(mint, giftRecipient.publicKey),
+        metadata: findMetadataPda(mint),
+        masterEdition: findMasterEditionPda(mint),
+        collectionMetadata: findMetadataPda(collectionMintPda),
+        strategyGenes: null,
+        tokenRecord: null,
+        owner: giftRecipient.publicKey,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([giftRecipient])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(strategyNftPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(historyPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(findMetadataPda(mint))).to.be.null;
+    expect(await provider.connection.getBalance(giftRecipient.publicKey)).to.be.greaterThan(0);
+
+    const configAfter = await program.account.collectionConfig.fetch(collectionConfigPda);
+    expect(configAfter.totalBurned.toNumber()).to.equal(configBefore.totalBurned.toNumber() + 1);
+  });
+
+  it("closes the published genes when burning", async () => {
+    const strategyId = "test-strategy-genes-burn";
+    const mint = Keypair.generate();
+    const genes = { ...sampleGenes, takeProfitBps: 15_000 };
+
+    const [strategyNftPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from(strategyId)],
+      program.programId
+    );
+    const genesPda = findGenesPda(program.programId, mint.publicKey);
+    const genesHash = sha256(program.coder.types.encode("StrategyGenes", genes));
+
+    const attestation: MintAttestation = {
+      strategyId,
+      genesHash,
+      archetype: "balanced",
+      generation: 1,
+      fitnessScore: new anchor.BN(5000),
+      totalPnl: new anchor.BN(10000),
+      winRate: new anchor.BN(5000),
+      tradesExecuted: 10,
+      sharpeRatioMilli: 500,
+      maxDrawdownBps: 1000,
+      avgHoldTimeSecs: 600,
+      parentA: null,
+      parentB: null,
+      royaltyOverride: null,
+      owner: authority.publicKey,
+      expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 300),
+    };
+
+    await mintStrategy(attestation, { mint, genes });
+
+    await program.methods
+      .burnStrategyNft()
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        strategyNft: strategyNftPda,
+        performanceHistory: findHistoryPda(program.programId, mint.publicKey),
+        strategyGenes: genesPda,
+        mint: mint.publicKey,
+        tokenAccount: await getAssociatedTokenAddress(mint.publicKey, authority.publicKey),
+        metadata: findMetadataPda(mint.publicKey),
+        masterEdition: findMasterEditionPda(mint.publicKey),
+        collectionMetadata: findMetadataPda(collectionMintPda),
+        tokenRecord: null,
+        owner: authority.publicKey,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(genesPda)).to.be.null;
+    // The registry keeps the genes from being minted again under another id
+    expect(
+      await provider.connection.getAccountInfo(findGenesRegistryPda(program.programId, genesHash))
+    ).to.not.be.null;
+  });
+
+  it("migrates a legacy strategy NFT without a collection and burns it", async () => {
+    const mint = Keypair.fromSeed(LEGACY_MINT_SEED);
+    const genesHash = sha256("legacyhash");
+    const [strategyNftPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from(LEGACY_STRATEGY_ID)],
+      program.programId
+    );
+    const historyPda = findHistoryPda(program.programId, mint.publicKey);
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // Recreate the legacy NFT's mint and metadata the fixture account points at
+    await createMint(
+      provider.connection,
+      payer,
+      authority.publicKey,
+      authority.publicKey,
+      0,
+      mint
+    );
+    const tokenAccount = await createAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      mint.publicKey,
+      authority.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      payer,
+      mint.publicKey,
+      tokenAccount,
+      authority.publicKey,
+      1
+    );
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        ...createLegacyMetadataInstructions(mint.publicKey, authority.publicKey)
+      )
+    );
+
+    await program.methods
+      .migrateStrategyNft(LEGACY_STRATEGY_ID, genesHash)
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        strategyNft: strategyNftPda,
+        mint: mint.publicKey,
+        performanceHistory: historyPda,
+        genesRegistry: findGenesRegistryPda(program.programId, genesHash),
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const migrated = await program.account.strategyNftData.fetch(strategyNftPda);
+    expect(migrated.mint.toBase58()).to.equal(mint.publicKey.toBase58());
+    expect(migrated.genesHash).to.deep.equal(genesHash);
+
+    await program.methods
+      .burnStrategyNft()
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        strategyNft: strategyNftPda,
+        performanceHistory: historyPda,
+        mint: mint.publicKey,
+        tokenAccount,
+        metadata: findMetadataPda(mint.publicKey),
+        masterEdition: findMasterEditionPda(mint.publicKey),
+        // Not verified in the collection, so the program leaves this out of the burn
+        collectionMetadata: findMetadataPda(collectionMintPda),
+        strategyGenes: null,
+        tokenRecord: null,
+        owner: authority.publicKey,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(strategyNftPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(historyPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(findMetadataPda(mint.publicKey))).to.be.null;
+  });
+
+  it("mints, transfers and burns a programmable NFT", async () => {
+    const strategyId = "test-strategy-pnft";
+    const mint = Keypair.generate();
+    const receiver = Keypair.generate();
+    const [strategyNftPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from(strategyId)],
+      program.programId
+    );
+
+    await program.methods
+      .updateTokenStandard({ programmableNonFungible: {} }, FOUNDATION_RULE_SET)
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const attestation: MintAttestation = {
+      strategyId,
+      genesHash: sha256("pnfthash"),
+      archetype: "balanced",
+      generation: 10,
+      fitnessScore: new anchor.BN(8800),
+      totalPnl: new anchor.BN(4000000),
+      winRate: new anchor.BN(6500),
+      tradesExecuted: 280,
+      sharpeRatioMilli: 1900,
+      maxDrawdownBps: 1000,
+      avgHoldTimeSecs: 3600,
+      parentA: null,
+      parentB: null,
+      royaltyOverride: null,
+      owner: authority.publicKey,
+      expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 300),
+    };
+    const tokenAccount = await getAssociatedTokenAddress(mint.publicKey, authority.publicKey);
+
+    await mintStrategy(attestation, {
+      mint,
+      accounts: {
+        tokenRecord: findTokenRecordPda(mint.publicKey, tokenAccount),
+        authorizationRules: FOUNDATION_RULE_SET,
+        authorizationRulesProgram: TOKEN_AUTH_RULES_PROGRAM_ID,
+      },
+      preInstructions: [
+        anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 800_000 }),
+      ],
+    });
+
+    let strategyNft = await program.account.strategyNftData.fetch(strategyNftPda);
+    expect(strategyNft.tokenStandard).to.deep.equal({ programmableNonFungible: {} });
+    // pNFT token accounts stay frozen; only Metaplex can move them
+    expect((await getAccount(provider.connection, tokenAccount)).isFrozen).to.be.true;
+
+    const receiverTokenAccount = await getAssociatedTokenAddress(
+      mint.publicKey,
+      receiver.publicKey
+    );
+    await program.methods
+      .transferStrategyNft()
+      .accounts({
+        strategyNft: strategyNftPda,
+        mint: mint.publicKey,
+        tokenAccount,
+        destinationOwner: receiver.publicKey,
+        destinationToken: receiverTokenAccount,
+        metadata: findMetadataPda(mint.publicKey),
+        masterEdition: findMasterEditionPda(mint.publicKey),
+        tokenRecord: findTokenRecordPda(mint.publicKey, tokenAccount),
+        destinationTokenRecord: findTokenRecordPda(mint.publicKey, receiverTokenAccount),
+        authorizationRules: FOUNDATION_RULE_SET,
+        authorizationRulesProgram: TOKEN_AUTH_RULES_PROGRAM_ID,
+        owner: authority.publicKey,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .preInstructions([
+        anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+      ])
+      .rpc();
+
+    strategyNft = await program.account.strategyNftData.fetch(strategyNftPda);
+    expect(strategyNft.owner.toString()).to.equal(receiver.publicKey.toString());
+    expect(
+      (await getAccount(provider.connection, receiverTokenAccount)).amount.toString()
+    ).to.equal("1");
+
+    await program.methods
+      .burnStrategyNft()
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        strategyNft: strategyNftPda,
+        performanceHistory: findHistoryPda(program.programId, mint.publicKey),
+        mint: mint.publicKey,
+        tokenAccount: receiverTokenAccount,
+        metadata: findMetadataPda(mint.publicKey),
+        masterEdition: findMasterEditionPda(mint.publicKey),
+        collectionMetadata: findMetadataPda(collectionMintPda),
+        strategyGenes: null,
+        tokenRecord: findTokenRecordPda(mint.publicKey, receiverTokenAccount),
+        owner: receiver.publicKey,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([receiver])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(strategyNftPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(findMetadataPda(mint.publicKey))).to.be.null;
+
+    await program.methods
+      .updateTokenStandard({ nonFungible: {} }, null)
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+  });
+
+  it("mints a Token-2022 strategy NFT with stats in its metadata", async () => {
+    const strategyId = "test-strategy-2022";
+    const mint = Keypair.generate();
+    const [strategyNftPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from(strategyId)],
+      program.programId
+    );
+
+    await program.methods
+      .updateTokenStandard({ token2022: {} }, null)
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const attestation: MintAttestation = {
+      strategyId,
+      genesHash: sha256("token2022hash"),
+      archetype: "momentum",
+      generation: 11,
+      fitnessScore: new anchor.BN(9150),
+      totalPnl: new anchor.BN(5000000),
+      winRate: new anchor.BN(7000),
+      tradesExecuted: 320,
+      sharpeRatioMilli: 2200,
+      maxDrawdownBps: 900,
+      avgHoldTimeSecs: 4200,
+      parentA: null,
+      parentB: null,
+      royaltyOverride: null,
+      owner: authority.publicKey,
+      expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 300),
+    };
+    const tokenAccount = await getAssociatedTokenAddress(
+      mint.publicKey,
+      authority.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    await program.methods
+      .mintStrategyNft2022(
+        attestation.strategyId,
+        attestation.genesHash,
+        attestation.archetype,
+        attestation.generation,
+        attestation.fitnessScore,
+        attestation.totalPnl,
+        attestation.winRate,
+        attestation.tradesExecuted,
+        attestation.sharpeRatioMilli,
+        attestation.maxDrawdownBps,
+        attestation.avgHoldTimeSecs,
+        attestation.parentA,
+        attestation.parentB,
+        attestation.expiresAt,
+        null
+      )
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        strategyNft: strategyNftPda,
+        genesRegistry: findGenesRegistryPda(program.programId, attestation.genesHash),
+        performanceHistory: findHistoryPda(program.programId, mint.publicKey),
+        strategyGenes: null,
+        parentANft: null,
+        parentBNft: null,
+        mint: mint.publicKey,
+        tokenAccount,
+        treasury: treasuryPda,
+        treasuryProgram: treasuryProgram.programId,
+        payer: authority.publicKey,
+        recipient: authority.publicKey,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .preInstructions([
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: engine.secretKey,
+          message: mintAttestationMessage(attestation),
+        }),
+      ])
+      .signers([mint])
+      .rpc();
+
+    const strategyNft = await program.account.strategyNftData.fetch(strategyNftPda);
+    expect(strategyNft.tokenStandard).to.deep.equal({ token2022: {} });
+    expect(
+      (
+        await getAccount(provider.connection, tokenAccount, undefined, TOKEN_2022_PROGRAM_ID)
+      ).amount.toString()
+    ).to.equal("1");
+
+    let metadata = await getTokenMetadata(provider.connection, mint.publicKey);
+    expect(metadata.symbol).to.equal("META");
+    expect(metadata.additionalMetadata).to.deep.equal([
+      ["fitness", "91.50"],
+      ["generation", "11"],
+      ["archetype", "momentum"],
+    ]);
+
+    // Stats updates reach the token metadata through a permissionless sync
+    await program.methods
+      .updateStrategyStats(
+        new anchor.BN(10025),
+        new anchor.BN(6000000),
+        new anchor.BN(7100),
+        350,
+        2300,
+        900,
+        4000,
+        null
+      )
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        strategyNft: strategyNftPda,
+        performanceHistory: findHistoryPda(program.programId, mint.publicKey),
+        metadata: null,
+        mint: null,
+        attestor: engine.publicKey,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([engine])
+      .rpc();
+
+    await program.methods
+      .syncTokenMetadata()
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        strategyNft: strategyNftPda,
+        mint: mint.publicKey,
+        payer: authority.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    metadata = await getTokenMetadata(provider.connection, mint.publicKey);
+    expect(metadata.additionalMetadata[0]).to.deep.equal(["fitness", "100.25"]);
+
+    await program.methods
+      .updateTokenStandard({ nonFungible: {} }, null)
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+  });
+
+  it("rejects royalty updates for Token-2022 NFTs", async () => {
+    const [strategyNftPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from("test-strategy-2022")],
+      program.programId
+    );
+    const { mint } = await program.account.strategyNftData.fetch(strategyNftPda);
+
+    try {
+      await program.methods
+        .updateNftRoyalties(null)
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          strategyNft: strategyNftPda,
+          metadata: findMetadataPda(mint),
+          attestor: engine.publicKey,
+          metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        })
+        .signers([engine])
+        .rpc();
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("UnsupportedTokenStandard");
+    }
+  });
+
+  it("changes a Token-2022 NFT's URI, then transfers and burns it", async () => {
+    const [strategyNftPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("strategy_nft"), Buffer.from("test-strategy-2022")],
+      program.programId
+    );
+    const { mint } = await program.account.strategyNftData.fetch(strategyNftPda);
+    const uri = "https://meta.io/nft/test-strategy-2022-v2.json";
+
+    const updateUri = (withMint: boolean) =>
+      program.methods
+        .updateStrategyStats(
+          new anchor.BN(10025),
+          new anchor.BN(6000000),
+          new anchor.BN(7100),
+          360,
+          2300,
+          900,
+          4000,
+          uri
+        )
+        .accounts({
+          collectionConfig: collectionConfigPda,
+          strategyNft: strategyNftPda,
+          performanceHistory: findHistoryPda(program.programId, mint),
+          metadata: null,
+          mint: withMint ? mint : null,
+          attestor: engine.publicKey,
+          metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          tokenProgram: withMint ? TOKEN_2022_PROGRAM_ID : null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([engine])
+        .rpc();
+
+    try {
+      await updateUri(false);
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("MissingMetadata");
+    }
+
+    await updateUri(true);
+    expect((await getTokenMetadata(provider.connection, mint)).uri).to.equal(uri);
+
+    const receiver = Keypair.generate();
+    const tokenAccount = await getAssociatedTokenAddress(
+      mint,
+      authority.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const receiverTokenAccount = await getAssociatedTokenAddress(
+      mint,
+      receiver.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    await program.methods
+      .transferStrategyNft()
+      .accounts({
+        strategyNft: strategyNftPda,
+        mint,
+        tokenAccount,
+        destinationOwner: receiver.publicKey,
+        destinationToken: receiverTokenAccount,
+        metadata: findMetadataPda(mint),
+        masterEdition: findMasterEditionPda(mint),
+        tokenRecord: null,
+        destinationTokenRecord: null,
+        authorizationRules: null,
+        authorizationRulesProgram: null,
+        owner: authority.publicKey,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const receiverTokens = await getAccount(
+      provider.connection,
+      receiverTokenAccount,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(receiverTokens.amount.toString()).to.equal("1");
+    const strategyNft = await program.account.strategyNftData.fetch(strategyNftPda);
+    expect(strategyNft.owner.toString()).to.equal(receiver.publicKey.toString());
+
+    await program.methods
+      .burnStrategyNft()
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        strategyNft: strategyNftPda,
+        performanceHistory: findHistoryPda(program.programId, mint),
+        strategyGenes: null,
+        mint,
+        tokenAccount: receiverTokenAccount,
+        metadata: findMetadataPda(mint),
+        masterEdition: findMasterEditionPda(mint),
+        collectionMetadata: findMetadataPda(collectionMintPda),
+        tokenRecord: null,
+        owner: receiver.publicKey,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        metadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([receiver])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(strategyNftPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(receiverTokenAccount)).to.be.null;
+    expect(
+      (await getMint(provider.connection, mint, undefined, TOKEN_2022_PROGRAM_ID)).supply.toString()
+    ).to.equal("0");
+  });
+
+  it("rejects a win rate above 100%", async () => {
+    const strategyId = "test-strategy-bad-stats";
+    const mint = Keypair.generate();
+
+    const attestation: MintAttestation = {
+      strategyId,
+      genesHash: sha256("badstatshash"),
+      archetype: "sniper",
+      generation: 1,
+      fitnessScore: new anchor.BN(1000),
+      totalPnl: new anchor.BN(0),
+      winRate: new anchor.BN(12000), // 120.00%
+      tradesExecuted: 3,
+      sharpeRatioMilli: 0,
+      maxDrawdownBps: 0,
+      avgHoldTimeSecs: 0,
+      parentA: null,
+      parentB: null,
+      royaltyOverride: null,
+      owner: authority.publicKey,
+      expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 300),
+    };
+
+    try {
+      await mintStrategy(attestation, { mint });
+
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("InvalidStrategyData");
+    }
+  });
+
+  it("toggles minting", async () => {
+    // Pause minting
+    await program.methods
+      .toggleMinting(false)
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    let config = await program.account.collectionConfig.fetch(collectionConfigPda);
+    expect(config.isActive).to.be.false;
+
+    // Resume minting
+    await program.methods
+      .toggleMinting(true)
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    config = await program.account.collectionConfig.fetch(collectionConfigPda);
+    expect(config.isActive).to.be.true;
+  });
+
+  it("fails to mint when paused", async () => {
+    // Pause minting first
+    await program.methods
+      .toggleMinting(false)
+      .accounts({
+        collectionConfig: collectionConfigPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const strategyId = "test-strategy-002";
+    const mint = Keypair.generate();
+
+    const attestation: MintAttestation = {
+      strategyId,
+      genesHash: sha256("hash456"),
+      archetype: "conservative",
+      generation: 3,
+      fitnessScore: new anchor.BN(7000),
+      totalPnl: new anchor.BN(500000),
+      winRate: new anchor.BN(5500),
+      tradesExecuted: 75,
+      sharpeRatioMilli: 900,
+      maxDrawdownBps: 2500,
+      avgHoldTimeSecs: 3600,
+      parentA: null,
+      parentB: null,
+      royaltyOverride: null,
+      owner: authority.publicKey,
+      expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 300),
+    };
+
+    try {
+      await mintStrategy(attestation, { mint });
+
       expect.fail("Should have thrown an error");
     } catch (error) {
       expect(error.message).to.include("MintingPaused");
@@ -2,7 +2,37 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { MetaTreasury } from "../target/types/meta_treasury";
 import { expect } from "chai";
-import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import {
+  Ed25519Program,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  burn,
+  createAssociatedTokenAccount,
+  createMint,
+  getAccount,
+  mintTo,
+  getAssociatedTokenAddress,
+} from "@solana/spl-token";
+
+// Domain-separated bytes an attestor signs, matching `meta_attestation::attestation_message`
+const attestationMessage = (programId: PublicKey, kind: string, payload: Buffer): Buffer => {
+  const kindBytes = Buffer.from(kind);
+  const kindLen = Buffer.alloc(4);
+  kindLen.writeUInt32LE(kindBytes.length);
+  return Buffer.concat([
+    Buffer.from("meta-attestation:v1:"),
+    programId.toBuffer(),
+    kindLen,
+    kindBytes,
+    payload,
+  ]);
+};
 
 describe("meta_treasury", () => {
   const provider = anchor.AnchorProvider.env();
@@ -11,15 +41,21 @@ describe("meta_treasury", () => {
   const program = anchor.workspace.MetaTreasury as Program<MetaTreasury>;
   const authority = provider.wallet;
   const multisig = Keypair.generate();
+  const engine = Keypair.generate();
   
   let treasuryPda: PublicKey;
   let treasuryBump: number;
+  let shareMintPda: PublicKey;
 
   before(async () => {
     [treasuryPda, treasuryBump] = PublicKey.findProgramAddressSync(
       [Buffer.from("treasury")],
       program.programId
     );
+    [shareMintPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("share_mint")],
+      program.programId
+    );
 
     // Airdrop to multisig for testing
     const sig = await provider.connection.requestAirdrop(
@@ -49,6 +85,28 @@ describe("meta_treasury", () => {
     expect(treasuryAccount.totalSol.toNumber()).to.equal(initialAmount.toNumber());
     expect(treasuryAccount.profitPool.toNumber()).to.equal(0);
     expect(treasuryAccount.isInitialized).to.be.true;
+    expect(treasuryAccount.version).to.equal(1);
+  });
+
+  it("rejects migrating a treasury that already uses the current layout", async () => {
+    try {
+      await program.methods
+        .migrateTreasury()
+        .accounts({
+          treasury: treasuryPda,
+          migration: PublicKey.findProgramAddressSync(
+            [Buffer.from("treasury_migration")],
+            program.programId
+          )[0],
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("AlreadyMigrated");
+    }
   });
 
   it("adds profits to the pool", async () => {
@@ -73,6 +131,300 @@ describe("meta_treasury", () => {
     expect(treasuryAfter.profitPool.toNumber()).to.equal(profitAmount.toNumber());
   });
 
+  // Submit a PnL report signed by `engine` and return its record address
+  const submitReport = async (cycleId: anchor.BN, realizedPnl: anchor.BN) => {
+    const tradesHash = Buffer.alloc(32, 9);
+    const report = Buffer.concat([
+      cycleId.toArrayLike(Buffer, "le", 8),
+      realizedPnl.toTwos(64).toArrayLike(Buffer, "le", 8),
+      new anchor.BN(1).toArrayLike(Buffer, "le", 4),
+      tradesHash,
+    ]);
+    const [pnlReportPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pnl_report"), cycleId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .addAttestedProfits({
+        cycleId,
+        realizedPnl,
+        tradeCount: 1,
+        tradesHash: Array.from(tradesHash),
+      })
+      .accounts({
+        treasury: treasuryPda,
+        pnlReport: pnlReportPda,
+        authority: authority.publicKey,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .preInstructions([
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: engine.secretKey,
+          message: attestationMessage(program.programId, "PnlReport", report),
+        }),
+      ])
+      .rpc();
+
+    return pnlReportPda;
+  };
+
+  it("adds profits from an engine-attested PnL report", async () => {
+    const cycleId = new anchor.BN(1);
+    const realizedPnl = new anchor.BN(0.2 * LAMPORTS_PER_SOL);
+    const tradeCount = 42;
+    const tradesHash = Buffer.alloc(32, 7);
+
+    await program.methods
+      .setEngineAttestor(engine.publicKey)
+      .accounts({
+        treasury: treasuryPda,
+        multisig: multisig.publicKey,
+      })
+      .signers([multisig])
+      .rpc();
+
+    // Borsh layout of PnlReport: u64 cycle_id, i64 realized_pnl, u32 trade_count, [u8; 32] trades_hash
+    const report = Buffer.concat([
+      cycleId.toArrayLike(Buffer, "le", 8),
+      realizedPnl.toTwos(64).toArrayLike(Buffer, "le", 8),
+      new anchor.BN(tradeCount).toArrayLike(Buffer, "le", 4),
+      tradesHash,
+    ]);
+    const [pnlReportPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pnl_report"), cycleId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    const treasuryBefore = await program.account.treasuryState.fetch(treasuryPda);
+
+    await program.methods
+      .addAttestedProfits({
+        cycleId,
+        realizedPnl,
+        tradeCount,
+        tradesHash: Array.from(tradesHash),
+      })
+      .accounts({
+        treasury: treasuryPda,
+        pnlReport: pnlReportPda,
+        authority: authority.publicKey,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .preInstructions([
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: engine.secretKey,
+          message: attestationMessage(program.programId, "PnlReport", report),
+        }),
+      ])
+      .rpc();
+
+    const treasuryAfter = await program.account.treasuryState.fetch(treasuryPda);
+    const record = await program.account.pnlReportRecord.fetch(pnlReportPda);
+
+    // Attested profits wait out the challenge period before reaching the pool or NAV
+    expect(treasuryAfter.pendingProfits.toNumber()).to.equal(
+      treasuryBefore.pendingProfits.toNumber() + realizedPnl.toNumber()
+    );
+    expect(treasuryAfter.totalSol.toNumber()).to.equal(treasuryBefore.totalSol.toNumber());
+    expect(treasuryAfter.profitPool.toNumber()).to.equal(treasuryBefore.profitPool.toNumber());
+    expect(record.report.tradeCount).to.equal(tradeCount);
+    expect(record.attestor.toString()).to.equal(engine.publicKey.toString());
+    expect(record.status).to.deep.equal({ pending: {} });
+  });
+
+  it("slashes the bond when a challenged report is upheld", async () => {
+    const challenger = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      challenger.publicKey,
+      0.5 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    const bond = new anchor.BN(0.1 * LAMPORTS_PER_SOL);
+    const [pnlReportPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pnl_report"), new anchor.BN(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [disputePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute"), pnlReportPda.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .setChallengeParams(new anchor.BN(24 * 60 * 60), bond)
+      .accounts({
+        treasury: treasuryPda,
+        multisig: multisig.publicKey,
+      })
+      .signers([multisig])
+      .rpc();
+
+    await program.methods
+      .challengeReport()
+      .accounts({
+        treasury: treasuryPda,
+        pnlReport: pnlReportPda,
+        dispute: disputePda,
+        challenger: challenger.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([challenger])
+      .rpc();
+
+    const treasuryBefore = await program.account.treasuryState.fetch(treasuryPda);
+
+    await program.methods
+      .resolveDispute(true)
+      .accounts({
+        treasury: treasuryPda,
+        pnlReport: pnlReportPda,
+        dispute: disputePda,
+        multisig: multisig.publicKey,
+        challenger: challenger.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([multisig])
+      .rpc();
+
+    const treasuryAfter = await program.account.treasuryState.fetch(treasuryPda);
+    const record = await program.account.pnlReportRecord.fetch(pnlReportPda);
+
+    expect(record.status).to.deep.equal({ finalized: {} });
+    expect(treasuryAfter.insuranceReserve.toNumber()).to.equal(
+      treasuryBefore.insuranceReserve.toNumber() + bond.toNumber()
+    );
+    expect(treasuryAfter.profitPool.toNumber()).to.equal(
+      treasuryBefore.profitPool.toNumber() + record.amount.toNumber()
+    );
+  });
+
+  it("refunds a report whose dispute is resolved as invalid", async () => {
+    const challenger = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      challenger.publicKey,
+      0.5 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    const bond = new anchor.BN(0.1 * LAMPORTS_PER_SOL);
+
+    // A free dispute would let anyone stall every report
+    try {
+      await program.methods
+        .setChallengeParams(new anchor.BN(60 * 60), new anchor.BN(0))
+        .accounts({
+          treasury: treasuryPda,
+          multisig: multisig.publicKey,
+        })
+        .signers([multisig])
+        .rpc();
+
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("InvalidChallengeParams");
+    }
+
+    await program.methods
+      .setChallengeParams(new anchor.BN(60 * 60), bond)
+      .accounts({
+        treasury: treasuryPda,
+        multisig: multisig.publicKey,
+      })
+      .signers([multisig])
+      .rpc();
+
+    const treasuryBefore = await program.account.treasuryState.fetch(treasuryPda);
+    const amount = new anchor.BN(0.1 * LAMPORTS_PER_SOL);
+    const pnlReportPda = await submitReport(new anchor.BN(2), amount);
+    const [disputePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute"), pnlReportPda.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .challengeReport()
+      .accounts({
+        treasury: treasuryPda,
+        pnlReport: pnlReportPda,
+        dispute: disputePda,
+        challenger: challenger.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([challenger])
+      .rpc();
+
+    const challengerBefore = await provider.connection.getBalance(challenger.publicKey);
+    const authorityBefore = await provider.connection.getBalance(authority.publicKey);
+
+    await program.methods
+      .resolveDispute(false)
+      .accounts({
+        treasury: treasuryPda,
+        pnlReport: pnlReportPda,
+        dispute: disputePda,
+        multisig: multisig.publicKey,
+        challenger: challenger.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([multisig])
+      .rpc();
+
+    const treasuryAfter = await program.account.treasuryState.fetch(treasuryPda);
+    const record = await program.account.pnlReportRecord.fetch(pnlReportPda);
+    const challengerAfter = await provider.connection.getBalance(challenger.publicKey);
+    const authorityAfter = await provider.connection.getBalance(authority.publicKey);
+
+    expect(record.status).to.deep.equal({ rejected: {} });
+    expect(treasuryAfter.pendingProfits.toNumber()).to.equal(
+      treasuryBefore.pendingProfits.toNumber()
+    );
+    expect(treasuryAfter.totalSol.toNumber()).to.equal(treasuryBefore.totalSol.toNumber());
+    expect(treasuryAfter.profitPool.toNumber()).to.equal(treasuryBefore.profitPool.toNumber());
+    // The challenger gets the bond back with the dispute rent; the authority pays the fee
+    expect(challengerAfter - challengerBefore).to.be.greaterThan(bond.toNumber());
+    expect(authorityAfter - authorityBefore).to.be.closeTo(amount.toNumber(), 10_000);
+  });
+
+  it("finalizes an undisputed report after the challenge period", async () => {
+    await program.methods
+      .setChallengeParams(new anchor.BN(0), new anchor.BN(0.1 * LAMPORTS_PER_SOL))
+      .accounts({
+        treasury: treasuryPda,
+        multisig: multisig.publicKey,
+      })
+      .signers([multisig])
+      .rpc();
+
+    const treasuryBefore = await program.account.treasuryState.fetch(treasuryPda);
+    const amount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
+    const pnlReportPda = await submitReport(new anchor.BN(3), amount);
+
+    await program.methods
+      .finalizeReport()
+      .accounts({
+        treasury: treasuryPda,
+        pnlReport: pnlReportPda,
+      })
+      .rpc();
+
+    const treasuryAfter = await program.account.treasuryState.fetch(treasuryPda);
+    const record = await program.account.pnlReportRecord.fetch(pnlReportPda);
+
+    expect(record.status).to.deep.equal({ finalized: {} });
+    expect(treasuryAfter.pendingProfits.toNumber()).to.equal(
+      treasuryBefore.pendingProfits.toNumber()
+    );
+    expect(treasuryAfter.profitPool.toNumber()).to.equal(
+      treasuryBefore.profitPool.toNumber() + amount.toNumber()
+    );
+    expect(treasuryAfter.totalSol.toNumber()).to.equal(
+      treasuryBefore.totalSol.toNumber() + amount.toNumber()
+    );
+  });
+
   it("distributes profits to a holder", async () => {
     const holder = Keypair.generate();
     const holderShareBps = 1000; // 10%
@@ -110,38 +462,766 @@ describe("meta_treasury", () => {
     );
   });
 
-  it("performs emergency withdrawal with multisig", async () => {
-    const destination = Keypair.generate();
-    const withdrawAmount = new anchor.BN(0.1 * LAMPORTS_PER_SOL);
-
-    // Airdrop minimum rent to destination
+  it("allocates and claims a distribution before it expires", async () => {
+    const holder = Keypair.generate();
     const sig = await provider.connection.requestAirdrop(
-      destination.publicKey,
+      holder.publicKey,
       0.01 * LAMPORTS_PER_SOL
     );
     await provider.connection.confirmTransaction(sig);
 
     const treasuryBefore = await program.account.treasuryState.fetch(treasuryPda);
-    const destBalanceBefore = await provider.connection.getBalance(destination.publicKey);
+    const [distributionPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("distribution"),
+        treasuryBefore.distributionCount.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const [claimPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("claim"), distributionPda.toBuffer(), holder.publicKey.toBuffer()],
+      program.programId
+    );
+    const amount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
+    const expiresAt = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
 
     await program.methods
-      .withdrawEmergency(withdrawAmount)
+      .createDistribution(amount, expiresAt)
       .accounts({
         treasury: treasuryPda,
-        multisig: multisig.publicKey,
-        destination: destination.publicKey,
+        distribution: distributionPda,
+        authority: authority.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
-      .signers([multisig])
       .rpc();
 
+    await program.methods
+      .allocateClaim(holder.publicKey, amount)
+      .accounts({
+        treasury: treasuryPda,
+        distribution: distributionPda,
+        claim: claimPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const holderBalanceBefore = await provider.connection.getBalance(holder.publicKey);
+
+    await program.methods
+      .claimDistribution()
+      .accounts({
+        treasury: treasuryPda,
+        distribution: distributionPda,
+        claim: claimPda,
+        holder: holder.publicKey,
+      })
+      .signers([holder])
+      .rpc();
+
+    const holderBalanceAfter = await provider.connection.getBalance(holder.publicKey);
+    const distribution = await program.account.distribution.fetch(distributionPda);
+
     const treasuryAfter = await program.account.treasuryState.fetch(treasuryPda);
-    const destBalanceAfter = await provider.connection.getBalance(destination.publicKey);
+    const claimRent = await provider.connection.getMinimumBalanceForRentExemption(
+      program.account.distributionClaim.size
+    );
 
-    expect(destBalanceAfter - destBalanceBefore).to.equal(withdrawAmount.toNumber());
-    expect(treasuryAfter.totalSol.toNumber()).to.equal(
-      treasuryBefore.totalSol.toNumber() - withdrawAmount.toNumber()
+    // The holder receives the payout and the closed claim's rent
+    expect(holderBalanceAfter - holderBalanceBefore).to.equal(amount.toNumber() + claimRent);
+    expect(distribution.claimedAmount.toNumber()).to.equal(amount.toNumber());
+    expect(treasuryAfter.outstandingDistributions.toNumber()).to.equal(
+      treasuryBefore.outstandingDistributions.toNumber()
+    );
+    expect(await provider.connection.getAccountInfo(claimPda)).to.be.null;
+  });
+
+  it("sweeps an expired distribution back into the profit pool", async () => {
+    const treasuryBefore = await program.account.treasuryState.fetch(treasuryPda);
+    const [distributionPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("distribution"),
+        treasuryBefore.distributionCount.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const amount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
+    const clusterTime = async () =>
+      (await provider.connection.getBlockTime(await provider.connection.getSlot())) ?? 0;
+    const expiresAt = (await clusterTime()) + 2;
+
+    await program.methods
+      .createDistribution(amount, new anchor.BN(expiresAt))
+      .accounts({
+        treasury: treasuryPda,
+        distribution: distributionPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // The set-aside amount leaves the pool but stays reserved outside the vault
+    const treasuryCreated = await program.account.treasuryState.fetch(treasuryPda);
+    expect(treasuryCreated.profitPool.toNumber()).to.equal(
+      treasuryBefore.profitPool.toNumber() - amount.toNumber()
+    );
+    expect(treasuryCreated.outstandingDistributions.toNumber()).to.equal(
+      treasuryBefore.outstandingDistributions.toNumber() + amount.toNumber()
+    );
+
+    while ((await clusterTime()) <= expiresAt) {
+      await new Promise((resolve) => setTimeout(resolve, 500));
+    }
+
+    await program.methods
+      .sweepExpired()
+      .accounts({
+        treasury: treasuryPda,
+        distribution: distributionPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const treasuryAfter = await program.account.treasuryState.fetch(treasuryPda);
+
+    expect(treasuryAfter.profitPool.toNumber()).to.equal(treasuryBefore.profitPool.toNumber());
+    expect(treasuryAfter.outstandingDistributions.toNumber()).to.equal(
+      treasuryBefore.outstandingDistributions.toNumber()
+    );
+    expect(treasuryAfter.totalSol.toNumber()).to.equal(treasuryBefore.totalSol.toNumber());
+    expect(await provider.connection.getAccountInfo(distributionPda)).to.be.null;
+  });
+
+  it("initializes the vault share mint", async () => {
+    const authorityShares = await getAssociatedTokenAddress(
+      shareMintPda,
+      authority.publicKey
     );
+
+    await program.methods
+      .initializeVault()
+      .accounts({
+        treasury: treasuryPda,
+        shareMint: shareMintPda,
+        authorityShares,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const treasuryAccount = await program.account.treasuryState.fetch(treasuryPda);
+    const shares = await getAccount(provider.connection, authorityShares);
+
+    // Profits, the insurance reserve and pending reports are owed outside the vault
+    const reserved =
+      treasuryAccount.profitPool.toNumber() +
+      treasuryAccount.insuranceReserve.toNumber() +
+      treasuryAccount.outstandingDistributions.toNumber();
+
+    expect(treasuryAccount.shareMint.toString()).to.equal(shareMintPda.toString());
+    expect(Number(shares.amount)).to.equal(treasuryAccount.totalSol.toNumber() - reserved);
+  });
+
+  it("deposits SOL for shares and redeems them", async () => {
+    const depositor = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      depositor.publicKey,
+      2 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    const depositorShares = await createAssociatedTokenAccount(
+      provider.connection,
+      depositor,
+      shareMintPda,
+      depositor.publicKey
+    );
+    const depositAmount = new anchor.BN(0.5 * LAMPORTS_PER_SOL);
+
+    await program.methods
+      .deposit(depositAmount)
+      .accounts({
+        treasury: treasuryPda,
+        shareMint: shareMintPda,
+        depositorShares,
+        depositor: depositor.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([depositor])
+      .rpc();
+
+    const minted = (await getAccount(provider.connection, depositorShares)).amount;
+    expect(Number(minted)).to.be.greaterThan(0);
+
+    const treasuryBefore = await program.account.treasuryState.fetch(treasuryPda);
+
+    await program.methods
+      .redeem(new anchor.BN(minted.toString()))
+      .accounts({
+        treasury: treasuryPda,
+        shareMint: shareMintPda,
+        ownerShares: depositorShares,
+        owner: depositor.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([depositor])
+      .rpc();
+
+    const treasuryAfter = await program.account.treasuryState.fetch(treasuryPda);
+    const remaining = (await getAccount(provider.connection, depositorShares)).amount;
+
+    expect(Number(remaining)).to.equal(0);
+    expect(
+      treasuryBefore.totalSol.toNumber() - treasuryAfter.totalSol.toNumber()
+    ).to.be.closeTo(depositAmount.toNumber(), 1);
+  });
+
+  it("queues a withdrawal and pays it once deployed capital returns", async () => {
+    const depositor = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      depositor.publicKey,
+      2 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    const depositorShares = await createAssociatedTokenAccount(
+      provider.connection,
+      depositor,
+      shareMintPda,
+      depositor.publicKey
+    );
+
+    await program.methods
+      .deposit(new anchor.BN(0.5 * LAMPORTS_PER_SOL))
+      .accounts({
+        treasury: treasuryPda,
+        shareMint: shareMintPda,
+        depositorShares,
+        depositor: depositor.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([depositor])
+      .rpc();
+
+    // Send every unreserved lamport out to the engine; reserved profits stay behind
+    const beforeDeploy = await program.account.treasuryState.fetch(treasuryPda);
+    const liquid =
+      beforeDeploy.totalSol.toNumber() -
+      beforeDeploy.profitPool.toNumber() -
+      beforeDeploy.insuranceReserve.toNumber() -
+      beforeDeploy.outstandingDistributions.toNumber() -
+      beforeDeploy.queuedWithdrawals.toNumber();
+    const deployed = new anchor.BN(liquid);
+
+    await program.methods
+      .deployCapital(deployed)
+      .accounts({
+        treasury: treasuryPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const treasuryBefore = await program.account.treasuryState.fetch(treasuryPda);
+    expect(treasuryBefore.profitPool.toNumber()).to.be.greaterThan(0);
+
+    const [withdrawalPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("withdrawal"),
+        treasuryBefore.withdrawalQueueTail.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const shares = (await getAccount(provider.connection, depositorShares)).amount;
+
+    await program.methods
+      .queueWithdrawal(new anchor.BN(shares.toString()))
+      .accounts({
+        treasury: treasuryPda,
+        withdrawalRequest: withdrawalPda,
+        shareMint: shareMintPda,
+        ownerShares: depositorShares,
+        owner: depositor.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([depositor])
+      .rpc();
+
+    const request = await program.account.withdrawalRequest.fetch(withdrawalPda);
+    expect(request.amount.toNumber()).to.be.closeTo(0.5 * LAMPORTS_PER_SOL, 1);
+
+    // Only the reserved profit lamports remain, and they cannot pay the queue
+    try {
+      await program.methods
+        .processWithdrawal()
+        .accounts({
+          treasury: treasuryPda,
+          withdrawalRequest: withdrawalPda,
+          owner: depositor.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("InsufficientLiquidity");
+    }
+
+    await program.methods
+      .returnCapital(deployed)
+      .accounts({
+        treasury: treasuryPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const ownerBalanceBefore = await provider.connection.getBalance(depositor.publicKey);
+
+    await program.methods
+      .processWithdrawal()
+      .accounts({
+        treasury: treasuryPda,
+        withdrawalRequest: withdrawalPda,
+        owner: depositor.publicKey,
+      })
+      .rpc();
+
+    const treasuryAfter = await program.account.treasuryState.fetch(treasuryPda);
+    const ownerBalanceAfter = await provider.connection.getBalance(depositor.publicKey);
+
+    // The payout plus the refunded request rent lands with the owner
+    expect(ownerBalanceAfter - ownerBalanceBefore).to.be.at.least(request.amount.toNumber());
+    expect(treasuryAfter.queuedWithdrawals.toNumber()).to.equal(0);
+    expect(treasuryAfter.withdrawalQueueHead.toNumber()).to.equal(
+      treasuryBefore.withdrawalQueueTail.toNumber() + 1
+    );
+    expect(treasuryAfter.profitPool.toNumber()).to.equal(treasuryBefore.profitPool.toNumber());
+    expect(await provider.connection.getAccountInfo(withdrawalPda)).to.be.null;
+  });
+
+  it("rejects deposits while the vault holds NAV but no shares", async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    const authorityShares = await getAssociatedTokenAddress(
+      shareMintPda,
+      authority.publicKey
+    );
+
+    // Burning the seed shares outside the program leaves NAV with nobody to price it against
+    const seedShares = (await getAccount(provider.connection, authorityShares)).amount;
+    await burn(provider.connection, payer, authorityShares, shareMintPda, payer, seedShares);
+
+    const treasuryAccount = await program.account.treasuryState.fetch(treasuryPda);
+    expect(treasuryAccount.totalSol.toNumber()).to.be.greaterThan(
+      treasuryAccount.profitPool.toNumber() + treasuryAccount.insuranceReserve.toNumber()
+    );
+
+    const depositor = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      depositor.publicKey,
+      LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    const depositorShares = await createAssociatedTokenAccount(
+      provider.connection,
+      depositor,
+      shareMintPda,
+      depositor.publicKey
+    );
+
+    try {
+      await program.methods
+        .deposit(new anchor.BN(0.1 * LAMPORTS_PER_SOL))
+        .accounts({
+          treasury: treasuryPda,
+          shareMint: shareMintPda,
+          depositorShares,
+          depositor: depositor.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([depositor])
+        .rpc();
+
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("NavWithoutShares");
+    }
+  });
+
+  it("creates a governance proposal and records votes", async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    const metaMint = await createMint(provider.connection, payer, authority.publicKey, null, 6);
+    const voterTokens = await createAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      metaMint,
+      authority.publicKey
+    );
+    await mintTo(provider.connection, payer, metaMint, voterTokens, payer, 1_000_000);
+
+    const [governancePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("governance")],
+      program.programId
+    );
+    const [governanceVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("governance_vault")],
+      program.programId
+    );
+    const [voterRecord] = PublicKey.findProgramAddressSync(
+      [Buffer.from("voter"), authority.publicKey.toBuffer()],
+      program.programId
+    );
+    const [proposalPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("proposal"), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [voteRecord] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vote"), proposalPda.toBuffer(), authority.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const params = {
+      quorumVotes: new anchor.BN(500_000),
+      thresholdBps: 5000,
+      proposalMinDeposit: new anchor.BN(1),
+      votingPeriod: new anchor.BN(60),
+      timelockDelay: new anchor.BN(60),
+    };
+    const initializeGovernance = (overrides: Partial<typeof params>) =>
+      program.methods
+        .initializeGovernance({ ...params, ...overrides })
+        .accounts({
+          treasury: treasuryPda,
+          governance: governancePda,
+          metaMint,
+          governanceVault,
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    // A zero quorum or proposal deposit would let one token pass anything
+    for (const overrides of [
+      { quorumVotes: new anchor.BN(0) },
+      { proposalMinDeposit: new anchor.BN(0) },
+    ]) {
+      try {
+        await initializeGovernance(overrides);
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("InvalidGovernanceParams");
+      }
+    }
+
+    await initializeGovernance({});
+
+    await program.methods
+      .depositVotes(new anchor.BN(1_000_000))
+      .accounts({
+        governance: governancePda,
+        governanceVault,
+        voterRecord,
+        voterTokens,
+        voter: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .createProposal({ updateMultisig: { newMultisig: Keypair.generate().publicKey } })
+      .accounts({
+        governance: governancePda,
+        proposal: proposalPda,
+        voterRecord,
+        proposer: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .castVote(true)
+      .accounts({
+        proposal: proposalPda,
+        voterRecord,
+        voteRecord,
+        voter: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const proposal = await program.account.proposal.fetch(proposalPda);
+    expect(proposal.votesFor.toNumber()).to.equal(1_000_000);
+    expect(proposal.votesAgainst.toNumber()).to.equal(0);
+    expect(proposal.state).to.deep.equal({ voting: {} });
+  });
+
+  it("performs emergency withdrawal with multisig", async () => {
+    const destination = Keypair.generate();
+    const withdrawAmount = new anchor.BN(0.1 * LAMPORTS_PER_SOL);
+
+    // Airdrop minimum rent to destination
+    const sig = await provider.connection.requestAirdrop(
+      destination.publicKey,
+      0.01 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    const treasuryBefore = await program.account.treasuryState.fetch(treasuryPda);
+    const destBalanceBefore = await provider.connection.getBalance(destination.publicKey);
+    const reserved =
+      treasuryBefore.profitPool.toNumber() +
+      treasuryBefore.insuranceReserve.toNumber() +
+      treasuryBefore.outstandingDistributions.toNumber();
+    const liquid =
+      treasuryBefore.totalSol.toNumber() - reserved - treasuryBefore.queuedWithdrawals.toNumber();
+    expect(reserved).to.be.greaterThan(0);
+
+    // SOL owed to holders and queued withdrawals is out of reach
+    try {
+      await program.methods
+        .withdrawEmergency(new anchor.BN(liquid + 1))
+        .accounts({
+          treasury: treasuryPda,
+          multisig: multisig.publicKey,
+          destination: destination.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([multisig])
+        .rpc();
+
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("InsufficientFunds");
+    }
+
+    await program.methods
+      .withdrawEmergency(withdrawAmount)
+      .accounts({
+        treasury: treasuryPda,
+        multisig: multisig.publicKey,
+        destination: destination.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([multisig])
+      .rpc();
+
+    const treasuryAfter = await program.account.treasuryState.fetch(treasuryPda);
+    const destBalanceAfter = await provider.connection.getBalance(destination.publicKey);
+
+    expect(destBalanceAfter - destBalanceBefore).to.equal(withdrawAmount.toNumber());
+    expect(treasuryAfter.totalSol.toNumber()).to.equal(
+      treasuryBefore.totalSol.toNumber() - withdrawAmount.toNumber()
+    );
+    // The withdrawal comes out of vault NAV, never out of reserved SOL
+    expect(treasuryAfter.profitPool.toNumber()).to.equal(treasuryBefore.profitPool.toNumber());
+    expect(treasuryAfter.outstandingDistributions.toNumber()).to.equal(
+      treasuryBefore.outstandingDistributions.toNumber()
+    );
+  });
+
+  it("lets guardians propose a recovery that the multisig can veto", async () => {
+    const guardian = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      guardian.publicKey,
+      0.1 * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    const { recoveryCount } = await program.account.treasuryState.fetch(treasuryPda);
+    const [recoveryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("recovery"), recoveryCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    await program.methods
+      .setGuardians([guardian.publicKey], 1, new anchor.BN(7 * 24 * 60 * 60))
+      .accounts({
+        treasury: treasuryPda,
+        multisig: multisig.publicKey,
+      })
+      .signers([multisig])
+      .rpc();
+
+    await program.methods
+      .proposeRecovery(Keypair.generate().publicKey)
+      .accounts({
+        treasury: treasuryPda,
+        recovery: recoveryPda,
+        guardian: guardian.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([guardian])
+      .rpc();
+
+    const recovery = await program.account.recoveryRequest.fetch(recoveryPda);
+    expect(recovery.approvals.length).to.equal(1);
+    expect(recovery.executableAt.toNumber()).to.be.greaterThan(0);
+
+    await program.methods
+      .vetoRecovery()
+      .accounts({
+        treasury: treasuryPda,
+        recovery: recoveryPda,
+        multisig: multisig.publicKey,
+        proposer: guardian.publicKey,
+      })
+      .signers([multisig])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(recoveryPda)).to.be.null;
+  });
+
+  it("rejects short timelocks and recoveries opened before a multisig change", async () => {
+    const guardians = [Keypair.generate(), Keypair.generate()];
+    for (const guardian of guardians) {
+      const sig = await provider.connection.requestAirdrop(
+        guardian.publicKey,
+        0.1 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+    }
+    const rotated = Keypair.generate();
+    const findRecoveryPda = (id: anchor.BN) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("recovery"), id.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+    const setGuardians = (timelock: number) =>
+      program.methods
+        .setGuardians(
+          guardians.map((guardian) => guardian.publicKey),
+          2,
+          new anchor.BN(timelock)
+        )
+        .accounts({
+          treasury: treasuryPda,
+          multisig: multisig.publicKey,
+        })
+        .signers([multisig])
+        .rpc();
+
+    // Anything shorter than the minimum would leave the multisig no window to veto
+    for (const timelock of [0, 7 * 24 * 60 * 60 - 1]) {
+      try {
+        await setGuardians(timelock);
+
+        expect.fail("Should have thrown an error");
+      } catch (error) {
+        expect(error.message).to.include("RecoveryTimelockTooShort");
+      }
+    }
+    await setGuardians(7 * 24 * 60 * 60);
+
+    // One guardian opens a proposal the other never approves
+    const { recoveryCount } = await program.account.treasuryState.fetch(treasuryPda);
+    const stalledPda = findRecoveryPda(recoveryCount);
+    await program.methods
+      .proposeRecovery(Keypair.generate().publicKey)
+      .accounts({
+        treasury: treasuryPda,
+        recovery: stalledPda,
+        guardian: guardians[1].publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([guardians[1]])
+      .rpc();
+
+    const recoveryPda = findRecoveryPda(recoveryCount.addn(1));
+    await program.methods
+      .proposeRecovery(Keypair.generate().publicKey)
+      .accounts({
+        treasury: treasuryPda,
+        recovery: recoveryPda,
+        guardian: guardians[0].publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([guardians[0]])
+      .rpc();
+
+    await program.methods
+      .approveRecovery()
+      .accounts({
+        treasury: treasuryPda,
+        recovery: recoveryPda,
+        guardian: guardians[1].publicKey,
+      })
+      .signers([guardians[1]])
+      .rpc();
+
+    const executeRecovery = () =>
+      program.methods
+        .executeRecovery()
+        .accounts({
+          treasury: treasuryPda,
+          recovery: recoveryPda,
+          proposer: guardians[0].publicKey,
+        })
+        .rpc();
+
+    // Quorum alone is not enough while the timelock runs
+    const recovery = await program.account.recoveryRequest.fetch(recoveryPda);
+    expect(recovery.executableAt.toNumber()).to.be.at.least(
+      recovery.proposedAt.toNumber() + 7 * 24 * 60 * 60
+    );
+    try {
+      await executeRecovery();
+
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("TimelockNotElapsed");
+    }
+
+    // A regular rotation invalidates every recovery proposed against the old multisig
+    await program.methods
+      .updateMultisig(rotated.publicKey)
+      .accounts({
+        treasury: treasuryPda,
+        multisig: multisig.publicKey,
+      })
+      .signers([multisig])
+      .rpc();
+
+    try {
+      await executeRecovery();
+
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("StaleRecovery");
+    }
+
+    try {
+      await program.methods
+        .approveRecovery()
+        .accounts({
+          treasury: treasuryPda,
+          recovery: stalledPda,
+          guardian: guardians[0].publicKey,
+        })
+        .signers([guardians[0]])
+        .rpc();
+
+      expect.fail("Should have thrown an error");
+    } catch (error) {
+      expect(error.message).to.include("StaleRecovery");
+    }
+
+    // Hand control back for the remaining tests
+    await program.methods
+      .updateMultisig(multisig.publicKey)
+      .accounts({
+        treasury: treasuryPda,
+        multisig: rotated.publicKey,
+      })
+      .signers([rotated])
+      .rpc();
   });
 
   it("updates the multisig authority", async () => {
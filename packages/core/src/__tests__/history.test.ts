@@ -0,0 +1,61 @@
+import { describe, it, expect } from "vitest";
+import {
+  decodePerformanceHistory,
+  PERFORMANCE_HISTORY_CAPACITY,
+  PERFORMANCE_HISTORY_DISCRIMINATOR,
+} from "../history.js";
+
+const SNAPSHOT_SIZE = 40;
+
+// Encode an account the way the program's `PerformanceHistory::push` leaves it
+function encodeHistory(trades: number[]): Uint8Array {
+  const data = new Uint8Array(8 + 32 + 2 + SNAPSHOT_SIZE * PERFORMANCE_HISTORY_CAPACITY + 1);
+  const view = new DataView(data.buffer);
+  data.set(PERFORMANCE_HISTORY_DISCRIMINATOR, 0);
+  data.fill(7, 8, 40);
+
+  let head = 0;
+  let len = 0;
+  for (const tradesExecuted of trades) {
+    const at = 42 + head * SNAPSHOT_SIZE;
+    view.setBigInt64(at, BigInt(1_700_000_000 + tradesExecuted), true);
+    view.setUint32(at + 8, 3, true);
+    view.setBigUint64(at + 12, BigInt(tradesExecuted * 100), true);
+    view.setBigInt64(at + 20, BigInt(-tradesExecuted), true);
+    view.setBigUint64(at + 28, 5000n, true);
+    view.setUint32(at + 36, tradesExecuted, true);
+    head = (head + 1) % PERFORMANCE_HISTORY_CAPACITY;
+    len = Math.min(len + 1, PERFORMANCE_HISTORY_CAPACITY);
+  }
+  data[40] = head;
+  data[41] = len;
+  data[data.length - 1] = 254;
+  return data;
+}
+
+describe("decodePerformanceHistory", () => {
+  it("returns a partly filled buffer in insertion order", () => {
+    const history = decodePerformanceHistory(encodeHistory([1, 2, 3]));
+
+    expect(history.snapshots.map((s) => s.tradesExecuted)).toEqual([1, 2, 3]);
+    expect(history.snapshots[2].totalPnl).toBe(-3n);
+    expect(history.snapshots[2].fitnessScore).toBe(300n);
+    expect(history.mint).toEqual(new Uint8Array(32).fill(7));
+    expect(history.bump).toBe(254);
+  });
+
+  it("drops the oldest entries once the buffer wraps", () => {
+    const writes = Array.from({ length: PERFORMANCE_HISTORY_CAPACITY + 5 }, (_, i) => i + 1);
+    const history = decodePerformanceHistory(encodeHistory(writes));
+
+    expect(history.snapshots).toHaveLength(PERFORMANCE_HISTORY_CAPACITY);
+    expect(history.snapshots.map((s) => s.tradesExecuted)).toEqual(writes.slice(5));
+  });
+
+  it("rejects other accounts", () => {
+    const data = encodeHistory([1]);
+    data[0] ^= 0xff;
+
+    expect(() => decodePerformanceHistory(data)).toThrow("not a PerformanceHistory");
+  });
+});
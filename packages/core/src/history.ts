@@ -0,0 +1,82 @@
+/**
+ * Decoder for the meta_nft `PerformanceHistory` account (`["history", mint]`),
+ * a ring buffer of the last `PERFORMANCE_HISTORY_CAPACITY` stat snapshots.
+ */
+
+/** Number of snapshots the on-chain ring buffer holds (`HISTORY_CAPACITY`) */
+export const PERFORMANCE_HISTORY_CAPACITY = 32;
+
+/** Anchor account discriminator, sha256("account:PerformanceHistory")[..8] */
+export const PERFORMANCE_HISTORY_DISCRIMINATOR = Uint8Array.from([
+  244, 111, 237, 4, 112, 212, 234, 47,
+]);
+
+// i64 timestamp, u32 generation, u64 fitness_score, i64 total_pnl, u64 win_rate, u32 trades_executed
+const SNAPSHOT_SIZE = 8 + 4 + 8 + 8 + 8 + 4;
+// discriminator, mint, head, len, snapshots, bump
+const ACCOUNT_SIZE = 8 + 32 + 1 + 1 + SNAPSHOT_SIZE * PERFORMANCE_HISTORY_CAPACITY + 1;
+
+export interface PerformanceSnapshot {
+  timestamp: bigint;
+  generation: number;
+  /** Hundredths, e.g. 9150n is 91.50 */
+  fitnessScore: bigint;
+  /** Lamports */
+  totalPnl: bigint;
+  /** Basis points */
+  winRate: bigint;
+  tradesExecuted: number;
+}
+
+export interface PerformanceHistory {
+  /** Raw 32-byte mint address */
+  mint: Uint8Array;
+  /** Snapshots from oldest to newest */
+  snapshots: PerformanceSnapshot[];
+  bump: number;
+}
+
+/**
+ * Decode raw `PerformanceHistory` account data and unroll the ring buffer so
+ * snapshots come back oldest first, matching `PerformanceHistory::snapshots()`.
+ */
+export function decodePerformanceHistory(data: Uint8Array): PerformanceHistory {
+  if (data.length < ACCOUNT_SIZE) {
+    throw new Error(`PerformanceHistory account is ${data.length} bytes, expected ${ACCOUNT_SIZE}`);
+  }
+  if (!PERFORMANCE_HISTORY_DISCRIMINATOR.every((byte, i) => data[i] === byte)) {
+    throw new Error("Account is not a PerformanceHistory");
+  }
+
+  const view = new DataView(data.buffer, data.byteOffset, data.byteLength);
+  const mint = data.slice(8, 40);
+  const head = data[40];
+  const len = data[41];
+  if (head >= PERFORMANCE_HISTORY_CAPACITY || len > PERFORMANCE_HISTORY_CAPACITY) {
+    throw new Error(`Invalid PerformanceHistory ring state: head ${head}, len ${len}`);
+  }
+
+  const readSnapshot = (slot: number): PerformanceSnapshot => {
+    const at = 42 + slot * SNAPSHOT_SIZE;
+    return {
+      timestamp: view.getBigInt64(at, true),
+      generation: view.getUint32(at + 8, true),
+      fitnessScore: view.getBigUint64(at + 12, true),
+      totalPnl: view.getBigInt64(at + 20, true),
+      winRate: view.getBigUint64(at + 28, true),
+      tradesExecuted: view.getUint32(at + 36, true),
+    };
+  };
+
+  // `head` is the next slot to write, so the oldest entry sits `len` slots behind it
+  const start = (head + PERFORMANCE_HISTORY_CAPACITY - len) % PERFORMANCE_HISTORY_CAPACITY;
+  const snapshots = Array.from({ length: len }, (_, i) =>
+    readSnapshot((start + i) % PERFORMANCE_HISTORY_CAPACITY)
+  );
+
+  return {
+    mint,
+    snapshots,
+    bump: data[42 + SNAPSHOT_SIZE * PERFORMANCE_HISTORY_CAPACITY],
+  };
+}
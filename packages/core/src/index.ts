@@ -1,3 +1,4 @@
 export * from "./types.js";
 export * from "./genetic.js";
 export * from "./utils.js";
+export * from "./history.js";